@@ -32,10 +32,22 @@ pub struct Cli {
     #[arg(short = 'F', long = "classify")]
     pub indicators: bool,
 
+    /// Append a + indicator at the end of the directories on which another filesystem is mounted
+    #[arg(long)]
+    pub mountpoint_indicator: bool,
+
     /// Display extended file metadata as a table
     #[arg(short, long)]
     pub long: bool,
 
+    /// List the extended attributes and their sizes under each entry in long mode
+    #[arg(short = '@', long)]
+    pub extended_attributes: bool,
+
+    /// List the POSIX ACL entries and their effective permissions under each entry in long mode
+    #[arg(long)]
+    pub acl: bool,
+
     /// Ignore the configuration file
     #[arg(long)]
     pub ignore_config: bool,
@@ -48,6 +60,10 @@ pub struct Cli {
     #[arg(long)]
     pub generate_config: bool,
 
+    /// How to format the output [default: default]
+    #[arg(long, value_name = "FORMAT", value_parser = ["default", "json", "ndjson", "csv", "tsv"])]
+    pub format: Option<String>,
+
     /// Display one entry per line
     #[arg(short = '1', long)]
     pub oneline: bool,
@@ -68,6 +84,14 @@ pub struct Cli {
     #[arg(long, value_name = "NUM")]
     pub depth: Option<usize>,
 
+    /// Do not recurse into directories on other filesystems, nor count them in the total size
+    #[arg(short = 'x', long)]
+    pub one_file_system: bool,
+
+    /// Number of threads used to walk directories, 0 for one per CPU [default: 1]
+    #[arg(long, value_name = "NUM")]
+    pub threads: Option<usize>,
+
     /// Display directories themselves, and not their contents (recursively when used with --tree)
     #[arg(short, long, conflicts_with = "recursive")]
     pub directory_only: bool,
@@ -80,15 +104,64 @@ pub struct Cli {
     #[arg(long, value_name = "MODE", value_parser = ["default", "short", "bytes"])]
     pub size: Option<String>,
 
+    /// Which size to display and sort by, the allocated size is the space used on disk [default: apparent]
+    #[arg(long, value_name = "MODE", value_parser = ["apparent", "allocated"])]
+    pub size_mode: Option<String>,
+
+    /// Show the number of entries of directories instead of their size, counting the entries
+    /// hidden by the filters too
+    #[arg(long)]
+    pub dir_count: bool,
+
+    /// Show the hash of the content of regular files, computed with ALGO [default: sha256]
+    #[arg(long, value_name = "ALGO", value_parser = ["sha256", "blake3", "crc32"])]
+    pub hash: Option<String>,
+
+    /// Do not hash the files larger than SIZE, e.g. 512M [default: 64M]
+    #[arg(long, value_name = "SIZE")]
+    pub hash_max_size: Option<String>,
+
+    /// Guess the type of regular files from their first bytes, for the icons and colors of the
+    /// files whose name is not enough
+    #[arg(long)]
+    pub sniff: bool,
+
     /// Display the total size of directories
     #[arg(long)]
     pub total_size: bool,
 
+    /// Cache the total size of unchanged directories on disk, a file growing in place is not
+    /// noticed until its directory changes
+    #[arg(long)]
+    pub total_size_cache: bool,
+
+    /// Recurse and list the groups of regular files with the same size and content, with the
+    /// space wasted by the copies
+    #[arg(long)]
+    pub duplicates: bool,
+
     /// How to display date [default: date] [possible values: date, locale, relative, +date-time-format]
     #[arg(long, value_parser = validate_date_argument)]
     pub date: Option<String>,
 
-    /// Sort by time modified
+    /// Which timestamp to display, sort and filter by [default: mtime]
+    #[arg(
+        long,
+        value_name = "WORD",
+        overrides_with_all = ["access_time", "change_time"],
+        value_parser = ["mtime", "modification", "atime", "access", "use", "ctime", "status", "btime", "birth", "creation"],
+    )]
+    pub time: Option<String>,
+
+    /// Use the time of last access instead of modification. Same as --time=atime
+    #[arg(short = 'u', overrides_with_all = ["time", "change_time"])]
+    pub access_time: bool,
+
+    /// Use the time of last status change instead of modification. Same as --time=ctime
+    #[arg(short = 'c', overrides_with_all = ["time", "access_time"])]
+    pub change_time: bool,
+
+    /// Sort by time (modification by default, see --time)
     #[arg(short = 't', long)]
     pub timesort: bool,
 
@@ -112,7 +185,7 @@ pub struct Cli {
     #[arg(
         long,
         value_name = "TYPE",
-        value_parser = ["size", "time", "version", "extension", "git", "none"],
+        value_parser = ["size", "time", "version", "extension", "git", "hash", "none"],
         overrides_with_all = ["timesort", "sizesort", "extensionsort", "versionsort", "gitsort", "no_sort"]
     )]
     pub sort: Option<String>,
@@ -137,10 +210,14 @@ pub struct Cli {
     #[arg(
     long,
     value_delimiter = ',',
-    value_parser = ["permission", "user", "group", "context", "size", "date", "name", "inode", "links", "git"],
+    value_parser = ["permission", "user", "group", "context", "size", "date", "mtime", "atime", "ctime", "btime", "name", "inode", "links", "xattrs", "acl", "caps", "chattr", "hash", "mime", "entries", "git", "git-commit", "git-author", "git-date", "git-diff"],
     )]
     pub blocks: Vec<String>,
 
+    /// Template of the lines of the long view, e.g. '{perm} {user:>8} {size:b} {mtime:%F} {name}'
+    #[arg(long, value_name = "TEMPLATE")]
+    pub format_template: Option<String>,
+
     /// Enable classic mode (display output similar to ls)
     #[arg(long)]
     pub classic: bool,
@@ -154,15 +231,78 @@ pub struct Cli {
     #[arg(short = 'I', long, value_name = "PATTERN")]
     pub ignore_glob: Vec<String>,
 
+    /// Do not display files/directories ignored by .gitignore, .ignore and the git excludes
+    #[arg(long)]
+    pub gitignore: bool,
+
+    /// Only display files/directories with names matching the glob pattern(s).
+    /// More than one can be specified by repeating the argument
+    #[arg(long, value_name = "PATTERN")]
+    pub include_glob: Vec<String>,
+
+    /// Only display entries of the given types: f (file), d (directory), l (symlink), p (pipe),
+    /// s (socket), b (block device), c (char device), x (executable)
+    #[arg(long, value_name = "TYPE", value_delimiter = ',', value_parser = ["f", "d", "l", "p", "s", "b", "c", "x"])]
+    pub r#type: Vec<String>,
+
+    /// Only display files with the given extensions, e.g. rs,toml
+    #[arg(long, value_name = "EXT", value_delimiter = ',')]
+    pub ext: Vec<String>,
+
+    /// Only display entries of at least this size, e.g. 512, 10K or 1.5M
+    #[arg(long, value_name = "SIZE")]
+    pub min_size: Option<String>,
+
+    /// Only display entries of at most this size, e.g. 512, 10K or 1.5M
+    #[arg(long, value_name = "SIZE")]
+    pub max_size: Option<String>,
+
+    /// Only display entries modified after this time (see --time), either relative like 2d
+    /// (s, m, h, d, w, y) or a date like 2025-01-01
+    #[arg(long, value_name = "TIME")]
+    pub newer: Option<String>,
+
+    /// Only display entries modified before this time (see --time), either relative like 2d
+    /// (s, m, h, d, w, y) or a date like 2025-01-01
+    #[arg(long, value_name = "TIME")]
+    pub older: Option<String>,
+
+    /// Only display entries owned by this user, given as a name or an id
+    #[arg(long, value_name = "NAME")]
+    pub user: Option<String>,
+
+    /// Only display entries belonging to this group, given as a name or an id
+    #[arg(long, value_name = "NAME")]
+    pub group: Option<String>,
+
     /// Display the index number of each file
     #[arg(short, long)]
     pub inode: bool,
 
     /// Show git status on file and directory
-    /// Only when used with --long option
+    /// Only when used with --long option or a machine readable --format
     #[arg(short, long)]
     pub git: bool,
 
+    /// Print the branch, upstream, stashes and dirty state of the Git repository before listing
+    /// a directory inside one
+    #[arg(long)]
+    pub git_summary: bool,
+
+    /// Do not display the files/directories ignored by Git, read from the Git status
+    #[arg(long)]
+    pub git_hide_ignored: bool,
+
+    /// Dim the files/directories ignored by Git, read from the Git status, even without the git
+    /// block
+    #[arg(long)]
+    pub git_dim_ignored: bool,
+
+    /// List the trees of a Git revision, like HEAD~2 or v1.0, instead of the files on the disk,
+    /// or the index with ':'
+    #[arg(long, value_name = "REV")]
+    pub git_rev: Option<String>,
+
     /// When showing file information for a symbolic link,
     /// show information for the file the link references rather than for the link itself
     #[arg(short = 'L', long)]
@@ -5,6 +5,7 @@ use std::path::Path;
 
 pub use crate::flags::color::ThemeOption;
 use crate::git::GitStatus;
+use crate::meta::Mime;
 use crate::print_output;
 use crate::theme::{Theme, color::ColorTheme};
 use jiff::{Span, SpanTotal, Timestamp, ToSpan, Unit};
@@ -38,6 +39,9 @@ pub enum Elem {
     Octal,
     Acl,
     Context,
+    Capabilities,
+    Immutable,
+    InodeFlag,
 
     /// Attributes
     Archive,
@@ -70,9 +74,32 @@ pub enum Elem {
 
     TreeEdge,
 
+    /// Content hash
+    Hash,
+
+    /// Number of entries of a directory
+    Entries,
+
+    /// Last commit touching a file, and its author
+    GitCommit,
+    GitAuthor,
+
     GitStatus {
         status: GitStatus,
     },
+
+    /// Name of an entry ignored by Git
+    GitIgnoredName,
+
+    /// Parts of the repository summary
+    GitBranch,
+    GitAhead,
+    GitBehind,
+    GitStash,
+
+    /// Line counts of the git-diff block
+    GitAdded,
+    GitRemoved,
 }
 
 impl Elem {
@@ -117,6 +144,9 @@ impl Elem {
             Elem::Octal => theme.permission.octal,
             Elem::Acl => theme.permission.acl,
             Elem::Context => theme.permission.context,
+            Elem::Capabilities => theme.permission.capabilities,
+            Elem::Immutable => theme.permission.immutable,
+            Elem::InodeFlag => theme.permission.inode_flag,
 
             Elem::Archive => theme.attributes.archive,
             Elem::AttributeRead => theme.attributes.read,
@@ -132,6 +162,10 @@ impl Elem {
             Elem::INode { valid: true } => theme.inode.valid,
             Elem::INode { valid: false } => theme.inode.invalid,
             Elem::TreeEdge => theme.tree_edge,
+            Elem::Hash => theme.hash,
+            Elem::Entries => theme.entries,
+            Elem::GitCommit => theme.git_commit,
+            Elem::GitAuthor => theme.git_author,
             Elem::Links { valid: false } => theme.links.invalid,
             Elem::Links { valid: true } => theme.links.valid,
 
@@ -162,9 +196,22 @@ impl Elem {
             Elem::GitStatus {
                 status: GitStatus::Modified,
             } => theme.git_status.modified,
+            Elem::GitStatus {
+                status: GitStatus::NewCommits,
+            } => theme.git_status.new_commits,
+            Elem::GitStatus {
+                status: GitStatus::Uninitialized,
+            } => theme.git_status.uninitialized,
             Elem::GitStatus {
                 status: GitStatus::Conflicted,
             } => theme.git_status.conflicted,
+            Elem::GitIgnoredName => theme.git_status.ignored_name,
+            Elem::GitBranch => theme.git_status.branch,
+            Elem::GitAhead => theme.git_status.ahead,
+            Elem::GitBehind => theme.git_status.behind,
+            Elem::GitStash => theme.git_status.stash,
+            Elem::GitAdded => theme.git_status.added,
+            Elem::GitRemoved => theme.git_status.removed,
             Elem::Date(_) | Elem::InvalidDate => {
                 // These are handled in style_default, not here
                 Color::Blue
@@ -273,7 +320,7 @@ impl Colors {
             }
 
             // Sort by timestamp (descending order - newest first)
-            timestamp_entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+            timestamp_entries.sort_by_key(|entry| std::cmp::Reverse(entry.timestamp));
 
             (t.date.older, timestamp_entries)
         } else {
@@ -300,6 +347,26 @@ impl Colors {
         }
     }
 
+    /// Colorize a name from the color of its MIME type in the theme, or else like
+    /// [colorize_using_path](Self::colorize_using_path).
+    pub fn colorize_using_mime(
+        &self,
+        input: String,
+        path: &Path,
+        mime: &Mime,
+        elem: &Elem,
+    ) -> ColoredString {
+        match self.style_from_mime(mime) {
+            Some(style) => style.apply(input),
+            None => self.colorize_using_path(input, path, elem),
+        }
+    }
+
+    /// Colorize a MIME type with its color in the theme, if it has one.
+    pub fn colorize_mime(&self, input: String, mime: &Mime) -> ColoredString {
+        self.style_from_mime(mime).unwrap_or_default().apply(input)
+    }
+
     pub fn default_style() -> ContentStyle {
         ContentStyle::default()
     }
@@ -311,6 +378,15 @@ impl Colors {
         }
     }
 
+    fn style_from_mime(&self, mime: &Mime) -> Option<ContentStyle> {
+        let theme = self.theme.as_ref()?;
+        let color = theme
+            .mime
+            .get(mime.essence())
+            .or_else(|| theme.mime.get(mime.category()))?;
+        Some(ContentStyle::default().with(*color))
+    }
+
     fn style(&self, elem: &Elem) -> ContentStyle {
         match &self.lscolors {
             Some(lscolors) => match self.get_indicator_from_elem(elem) {
@@ -493,6 +569,7 @@ mod elem {
     use super::Elem;
     use crate::theme::{color, color::ColorTheme};
     use crossterm::style::Color;
+    use std::collections::HashMap;
 
     #[cfg(test)]
     fn test_theme() -> ColorTheme {
@@ -508,6 +585,9 @@ mod elem {
                 octal: Color::AnsiValue(6),
                 acl: Color::DarkCyan,
                 context: Color::Cyan,
+                capabilities: Color::AnsiValue(202),
+                immutable: Color::AnsiValue(197),
+                inode_flag: Color::AnsiValue(6),
             },
             attributes: color::Attributes {
                 read: Color::Green,
@@ -558,8 +638,13 @@ mod elem {
                 valid: Color::AnsiValue(13),    // Pink
                 invalid: Color::AnsiValue(245), // Grey
             },
-            tree_edge: Color::AnsiValue(245), // Grey
+            tree_edge: Color::AnsiValue(245),  // Grey
+            hash: Color::AnsiValue(109),       // LightSkyBlue4
+            entries: Color::AnsiValue(116),    // DarkSlateGray3
+            git_commit: Color::AnsiValue(178), // Gold3
+            git_author: Color::AnsiValue(146), // LightSteelBlue3
             git_status: Default::default(),
+            mime: HashMap::new(),
         }
     }
 
@@ -2,11 +2,16 @@
 //! operations related to this.
 use crate::flags::HyperlinkOption;
 use crate::flags::display::Display;
+use crate::flags::format::FormatFlag;
+use crate::flags::hash::HashAlgorithm;
 use crate::flags::icons::{IconOption, IconTheme};
 use crate::flags::layout::Layout;
 use crate::flags::permission::PermissionFlag;
 use crate::flags::size::SizeFlag;
+use crate::flags::size_mode::SizeMode;
 use crate::flags::sorting::{DirGrouping, SortColumn};
+use crate::flags::time_field::TimeField;
+use crate::flags::type_filter::EntryKind;
 use crate::flags::{ColorOption, ThemeOption};
 use crate::print_error;
 
@@ -25,20 +30,42 @@ use std::io;
 pub struct Config {
     pub classic: Option<bool>,
     pub blocks: Option<Vec<String>>,
+    pub format_template: Option<String>,
     pub color: Option<Color>,
     pub date: Option<String>,
+    pub time: Option<TimeField>,
     pub dereference: Option<bool>,
     pub display: Option<Display>,
+    pub format: Option<FormatFlag>,
     pub icons: Option<Icons>,
     pub ignore_globs: Option<Vec<String>>,
+    pub gitignore: Option<bool>,
+    pub git_summary: Option<bool>,
+    pub git_hide_ignored: Option<bool>,
+    pub git_dim_ignored: Option<bool>,
+    pub include_globs: Option<Vec<String>>,
+    pub types: Option<Vec<EntryKind>>,
+    pub extensions: Option<Vec<String>>,
+    pub predicates: Option<Predicates>,
     pub indicators: Option<bool>,
+    pub mountpoint_indicator: Option<bool>,
+    pub extended_attributes: Option<bool>,
+    pub acl: Option<bool>,
     pub layout: Option<Layout>,
     pub recursion: Option<Recursion>,
+    pub one_file_system: Option<bool>,
     pub size: Option<SizeFlag>,
+    pub size_mode: Option<SizeMode>,
+    pub dir_count: Option<bool>,
+    pub hash: Option<Hash>,
+    pub sniff: Option<bool>,
     pub permission: Option<PermissionFlag>,
     pub sorting: Option<Sorting>,
     pub no_symlink: Option<bool>,
     pub total_size: Option<bool>,
+    pub total_size_cache: Option<bool>,
+    pub duplicates: Option<bool>,
+    pub threads: Option<usize>,
     pub symlink_arrow: Option<String>,
     pub hyperlink: Option<HyperlinkOption>,
     pub header: Option<bool>,
@@ -73,6 +100,24 @@ pub struct Sorting {
     pub dir_grouping: Option<DirGrouping>,
 }
 
+#[derive(Eq, PartialEq, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Predicates {
+    pub min_size: Option<String>,
+    pub max_size: Option<String>,
+    pub newer: Option<String>,
+    pub older: Option<String>,
+    pub user: Option<String>,
+    pub group: Option<String>,
+}
+
+#[derive(Eq, PartialEq, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Hash {
+    pub algorithm: Option<HashAlgorithm>,
+    pub max_size: Option<String>,
+}
+
 #[derive(Eq, PartialEq, Debug, Deserialize)]
 pub struct TruncateOwner {
     pub after: Option<usize>,
@@ -110,20 +155,42 @@ impl Config {
         Self {
             classic: None,
             blocks: None,
+            format_template: None,
             color: None,
             date: None,
+            time: None,
             dereference: None,
             display: None,
+            format: None,
             icons: None,
             ignore_globs: None,
+            gitignore: None,
+            git_summary: None,
+            git_hide_ignored: None,
+            git_dim_ignored: None,
+            include_globs: None,
+            types: None,
+            extensions: None,
+            predicates: None,
             indicators: None,
+            mountpoint_indicator: None,
+            extended_attributes: None,
+            acl: None,
             layout: None,
             recursion: None,
+            one_file_system: None,
             size: None,
+            size_mode: None,
+            dir_count: None,
+            hash: None,
+            sniff: None,
             permission: None,
             sorting: None,
             no_symlink: None,
             total_size: None,
+            total_size_cache: None,
+            duplicates: None,
+            threads: None,
             symlink_arrow: None,
             hyperlink: None,
             header: None,
@@ -224,7 +291,9 @@ classic: false
 # == Blocks ==
 # This specifies the columns and their order when using the long and the tree
 # layout.
-# Possible values: permission, user, group, context, size, date, name, inode, links, git
+# Possible values: permission, user, group, context, size, date, name, inode, links, xattrs, acl, caps, chattr, hash, mime, entries, git, git-commit, git-author, git-date, git-diff
+# The "date" block shows the timestamp selected by "time", while "mtime", "atime",
+# "ctime" and "btime" always show the given one and can be combined.
 blocks:
   - permission
   - user
@@ -233,6 +302,13 @@ blocks:
   - date
   - name
 
+# == Format template ==
+# This replaces the blocks of the long layout by a template of its lines. Each
+# `{field:[align][width][format]}` placeholder shows a block, aligned to the left
+# (<), the right (>) or the center (^) of a column at least `width` wide. Sizes
+# take a b, s or d format like "size", and dates a strftime format.
+# format-template: "{perm} {user:>8} {size:b} {mtime:%F} {name}"
+
 # == Color ==
 # This has various color options. (Will be expanded in the future.)
 color:
@@ -254,6 +330,11 @@ color:
 # `date_format` will be a `strftime` formatted value. e.g. `date: '+%d %b %y %X'` will give you a date like this: 17 Jun 21 20:14:55
 date: date
 
+# == Time ==
+# Which timestamp to display, sort and filter by.
+# Possible values: mtime, atime, ctime, btime
+# time: mtime
+
 # == Dereference ==
 # Whether to dereference symbolic links.
 # Possible values: false, true
@@ -264,6 +345,12 @@ dereference: false
 # Possible values: all, almost-all, directory-only
 # display: all
 
+# == Format ==
+# How to print the listing. "json" prints a single array, "ndjson" prints one
+# object per line, "csv" and "tsv" print the blocks as delimited columns.
+# Possible values: default, json, ndjson, csv, tsv
+# format: default
+
 # == Icons ==
 icons:
   # When to use icons.
@@ -282,11 +369,81 @@ icons:
 # ignore-globs:
 #   - .git
 
+# == Gitignore ==
+# Whether to hide the files ignored by the .gitignore, .ignore,
+# .git/info/exclude and global git excludes files.
+# Possible values: false, true
+# gitignore: false
+
+# == Git summary ==
+# Whether to print the branch, the commits ahead and behind its upstream, the
+# stashes and the dirty state of the Git repository before listing a directory
+# inside one.
+# Possible values: false, true
+# git-summary: false
+
+# == Git hide ignored ==
+# Whether to hide the files ignored by Git, as told by the Git status, even
+# without the "git" block.
+# Possible values: false, true
+# git-hide-ignored: false
+
+# == Git dim ignored ==
+# Whether to dim the files ignored by Git, as told by the Git status, even
+# without the "git" block, which dims them too.
+# Possible values: false, true
+# git-dim-ignored: false
+
+# == Include Globs ==
+# A list of globs the listed names have to match. In tree mode, the
+# directories without matching entries are hidden.
+# include-globs:
+#   - '*.rs'
+
+# == Types ==
+# The kinds of entries to list: f (file), d (directory), l (symlink), p (pipe),
+# s (socket), b (block device), c (char device), x (executable).
+# types: [f, l]
+
+# == Extensions ==
+# The extensions of the files to list.
+# extensions: [rs, toml]
+
+# == Predicates ==
+# The conditions on the size, modification time and owner of the listed entries.
+# Sizes are like 512, 10K or 1.5M, times either relative like 2d (s, m, h, d, w, y)
+# or dates like 2025-01-01.
+# predicates:
+#   min-size: 1M
+#   max-size: 1G
+#   newer: 2w
+#   older: 2025-01-01
+#   user: root
+#   group: wheel
+
 # == Indicators ==
 # Whether to add indicator characters to certain listed files.
 # Possible values: false, true
 indicators: false
 
+# == Mountpoint indicator ==
+# Whether to mark the directories on which another filesystem is mounted with a
+# `+` indicator.
+# Possible values: false, true
+# mountpoint-indicator: false
+
+# == Extended attributes ==
+# Whether to list the extended attributes and their sizes under each entry
+# in long mode.
+# Possible values: false, true
+# extended-attributes: false
+
+# == ACL ==
+# Whether to list the POSIX ACL entries, with their effective permissions,
+# under each entry in long mode.
+# Possible values: false, true
+# acl: false
+
 # == Layout ==
 # Which layout to use. "oneline" might be a bit confusing here and should be
 # called "one-per-line". It might be changed in the future.
@@ -302,11 +459,44 @@ recursion:
   # it unspecified for (virtually) infinite.
   # depth: 3
 
+# == One file system ==
+# Whether to stay on the filesystem of the listed paths when recursing or
+# computing the total size of directories.
+# Possible values: false, true
+# one-file-system: false
+
 # == Size ==
 # Specifies the format of the size column.
 # Possible values: default, short, bytes
 size: default
 
+# == Size mode ==
+# Specifies which size to display and sort by: the length of the files or the
+# space allocated for them on the disk, like `du --apparent-size` and `du`.
+# Possible values: apparent, allocated
+# size-mode: apparent
+
+# == Dir count ==
+# Whether the size column shows the number of entries of directories instead
+# of their size.
+# Possible values: false, true
+# dir-count: false
+
+# == Hash ==
+# How the "hash" block hashes the content of regular files. Larger files than
+# max-size are not hashed.
+# Possible values for algorithm: sha256, blake3, crc32
+# hash:
+#   algorithm: sha256
+#   max-size: 64M
+
+# == Sniff ==
+# Whether to guess the type of regular files from their first bytes, to pick
+# the icon and color of the files whose name is not enough. The icons and
+# colors of the types can be set in the "mime" section of the themes.
+# Possible values: false, true
+# sniff: false
+
 # == Permission ==
 # Specify the format of the permission column
 # Possible value: rwx, octal, attributes (windows only), disable
@@ -315,7 +505,7 @@ size: default
 # == Sorting ==
 sorting:
   # Specify what to sort by.
-  # Possible values: extension, name, time, size, version
+  # Possible values: extension, name, time, size, version, hash
   column: name
   # Whether to reverse the sorting.
   # Possible values: false, true
@@ -335,6 +525,24 @@ no-symlink: false
 # Possible values: false, true
 total-size: false
 
+# == Total size cache ==
+# Whether to cache the total size of directories on disk, keyed by the
+# modification time of each directory. A file growing in place is not noticed
+# until an entry of its directory is added, removed or renamed.
+# Possible values: false, true
+# total-size-cache: false
+
+# == Duplicates ==
+# Whether to recurse and list the groups of regular files with the same size
+# and content instead of the entries.
+# Possible values: false, true
+# duplicates: false
+
+# == Threads ==
+# How many threads to use when walking directories for the recursive and tree
+# layouts. 1 keeps the traversal sequential, 0 uses one thread per CPU.
+# threads: 1
+
 # == Hyperlink ==
 # Attach hyperlink to filenames
 # Possible values: always, auto, never
@@ -396,26 +604,45 @@ mod tests {
                     "date".into(),
                     "name".into(),
                 ]),
+                format_template: None,
                 color: Some(config_file::Color {
                     when: Some(ColorOption::Auto),
                     theme: Some(ThemeOption::Default)
                 }),
                 date: Some("date".to_string()),
+                time: None,
                 dereference: Some(false),
                 display: None,
+                format: None,
                 icons: Some(config_file::Icons {
                     when: Some(IconOption::Auto),
                     theme: Some(IconTheme::Fancy),
                     separator: Some(" ".to_string()),
                 }),
                 ignore_globs: None,
+                gitignore: None,
+                git_summary: None,
+                git_hide_ignored: None,
+                git_dim_ignored: None,
+                include_globs: None,
+                types: None,
+                extensions: None,
+                predicates: None,
                 indicators: Some(false),
+                mountpoint_indicator: None,
+                extended_attributes: None,
+                acl: None,
                 layout: Some(Layout::Grid),
                 recursion: Some(config_file::Recursion {
                     enabled: Some(false),
                     depth: None,
                 }),
+                one_file_system: None,
                 size: Some(SizeFlag::Default),
+                size_mode: None,
+                dir_count: None,
+                hash: None,
+                sniff: None,
                 permission: None,
                 sorting: Some(config_file::Sorting {
                     column: Some(SortColumn::Name),
@@ -424,6 +651,9 @@ mod tests {
                 }),
                 no_symlink: Some(false),
                 total_size: Some(false),
+                total_size_cache: None,
+                duplicates: None,
+                threads: None,
                 symlink_arrow: Some("⇒".into()),
                 hyperlink: Some(HyperlinkOption::Never),
                 header: Some(false),
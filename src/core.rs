@@ -1,21 +1,26 @@
 use crate::color::Colors;
 use crate::display;
+use crate::duplicates::{self, DuplicateGroup};
 use crate::flags::{
-    ColorOption, Display, Flags, HyperlinkOption, Layout, Literal, SortOrder, ThemeOption,
+    ColorOption, Display, Flags, FormatFlag, HyperlinkOption, Layout, Literal, SortOrder,
+    ThemeOption, Threads,
 };
 use crate::git::GitCache;
 use crate::icon::Icons;
+use crate::ignore_cache::IgnoreCache;
 
-use crate::meta::Meta;
+use crate::meta::{GitRev, Meta, SizeAccumulator};
 use crate::{ExitCode, print_error, print_output, sort};
-use std::path::PathBuf;
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 #[cfg(not(target_os = "windows"))]
 use std::io;
 #[cfg(not(target_os = "windows"))]
 use std::os::unix::io::AsRawFd;
 
-use crate::flags::blocks::Block;
 use crate::git_theme::GitTheme;
 #[cfg(target_os = "windows")]
 use terminal_size::terminal_size;
@@ -26,6 +31,7 @@ pub struct Core {
     colors: Colors,
     git_theme: GitTheme,
     sorters: Vec<(SortOrder, sort::SortFn)>,
+    thread_pool: Option<ThreadPool>,
 }
 
 impl Core {
@@ -85,17 +91,52 @@ impl Core {
 
         let sorters = sort::assemble_sorters(&flags);
 
+        let thread_pool = if flags.threads.is_parallel() {
+            match ThreadPoolBuilder::new()
+                .num_threads(flags.threads.0)
+                .build()
+            {
+                Ok(pool) => Some(pool),
+                Err(err) => {
+                    print_error!(
+                        "Cannot start the thread pool, walking sequentially: {}.",
+                        err
+                    );
+                    flags.threads = Threads::default();
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
         Self {
             flags,
             colors: Colors::new(color_theme),
             icons: Icons::new(tty_available, icon_when, icon_theme, icon_separator),
             git_theme: GitTheme::new(),
             sorters,
+            thread_pool,
         }
     }
 
     pub fn run(self, paths: Vec<PathBuf>) -> ExitCode {
-        let (mut meta_list, exit_code) = self.fetch(paths);
+        let (mut meta_list, exit_code) = match &self.thread_pool {
+            Some(pool) => pool.install(|| self.fetch(paths)),
+            None => self.fetch(paths),
+        };
+
+        if self.flags.duplicates.0 {
+            let mut groups = match &self.thread_pool {
+                Some(pool) => pool.install(|| duplicates::find(&meta_list, &self.flags)),
+                None => duplicates::find(&meta_list, &self.flags),
+            };
+            for group in &mut groups {
+                self.sort(&mut group.files);
+            }
+            self.display_duplicates(&groups);
+            return exit_code;
+        }
 
         self.sort(&mut meta_list);
         self.display(&meta_list);
@@ -105,9 +146,15 @@ impl Core {
     fn fetch(&self, paths: Vec<PathBuf>) -> (Vec<Meta>, ExitCode) {
         let mut exit_code = ExitCode::OK;
         let mut meta_list = Vec::with_capacity(paths.len());
+        // the path arguments whose last commit is displayed, by canonicalized parent directory,
+        // with the Git cache of the first of them
+        let mut commit_arguments: HashMap<PathBuf, (GitCache, Vec<(usize, String)>)> =
+            HashMap::new();
         let depth = match self.flags.layout {
             Layout::Tree => self.flags.recursion.depth,
-            _ if self.flags.recursion.enabled => self.flags.recursion.depth,
+            _ if self.flags.recursion.enabled || self.flags.duplicates.0 => {
+                self.flags.recursion.depth
+            }
             _ => 1,
         };
 
@@ -120,6 +167,22 @@ impl Core {
             .collect();
 
         for path in paths {
+            // the entries of a revision are built from the objects of the repository, their
+            // directories holding their total size already
+            if let Some(rev) = &self.flags.git_rev.0 {
+                match GitRev::open(&path, rev)
+                    .and_then(|git_rev| git_rev.meta(&path, depth, &self.flags))
+                {
+                    Ok(meta) if meta.matches_argument_filters(&self.flags) => meta_list.push(meta),
+                    Ok(_) => {}
+                    Err(err) => {
+                        print_error!("{}: {}.", path.display(), err);
+                        exit_code.set_if_greater(ExitCode::MajorIssue);
+                    }
+                }
+                continue;
+            }
+
             let mut meta =
                 match Meta::from_path(&path, self.flags.dereference.0, self.flags.permission) {
                     Ok(meta) => meta,
@@ -129,20 +192,46 @@ impl Core {
                         continue;
                     }
                 };
+            if !meta.matches_argument_filters(&self.flags) {
+                continue;
+            }
 
-            let cache = if self.flags.blocks.0.contains(&Block::GitStatus) {
+            let uses_git = self.flags.blocks.uses_git()
+                || self.flags.git_summary.0
+                || self.flags.git_hide_ignored.0
+                || self.flags.git_dim_ignored.0;
+            let cache = if uses_git {
                 Some(GitCache::new(&path))
             } else {
                 None
             };
 
+            let ignore = if self.flags.gitignore.0 {
+                IgnoreCache::new(&path)
+            } else {
+                None
+            };
+
             let recurse =
                 self.flags.layout == Layout::Tree || self.flags.display != Display::DirectoryOnly;
             if recurse {
-                match meta.recurse_into(depth, &self.flags, cache.as_ref()) {
+                match meta.recurse_into(depth, &self.flags, cache.as_ref(), ignore.as_ref()) {
                     Ok((content, path_exit_code)) => {
                         meta.content = content;
-                        meta.git_status = cache.and_then(|cache| cache.get(&meta.path, true));
+                        meta.set_git_status(
+                            cache.as_ref().and_then(|c| c.get(&meta.path, true)),
+                            &self.flags,
+                        );
+                        if self.flags.git_summary.0 && meta.content.is_some() {
+                            meta.repo_summary = cache.as_ref().and_then(GitCache::summary);
+                        }
+                        if let Some(cache) = cache
+                            .as_ref()
+                            .filter(|_| self.flags.blocks.displays_git_diff())
+                        {
+                            cache.read_diff_stat(&mut meta);
+                        }
+                        meta.read_block_data(&self.flags);
                         meta_list.push(meta);
                         exit_code.set_if_greater(path_exit_code);
                     }
@@ -153,14 +242,58 @@ impl Core {
                     }
                 };
             } else {
-                meta.git_status = cache.and_then(|cache| cache.get(&meta.path, true));
+                if self.flags.mountpoint_indicator.0 {
+                    meta.mark_mount_point();
+                }
+                meta.set_git_status(
+                    cache.as_ref().and_then(|c| c.get(&meta.path, true)),
+                    &self.flags,
+                );
+                if let Some(cache) = cache
+                    .as_ref()
+                    .filter(|_| self.flags.blocks.displays_git_diff())
+                {
+                    cache.read_diff_stat(&mut meta);
+                }
+                meta.read_block_data(&self.flags);
                 meta_list.push(meta);
             };
+
+            // the last commits are read once all the arguments of each directory are known
+            if let Some(cache) = cache.filter(|_| self.flags.blocks.displays_git_commit()) {
+                if let Some((dir, name)) = argument_dir(&path) {
+                    let (_, arguments) = commit_arguments
+                        .entry(dir)
+                        .or_insert_with(|| (cache, Vec::new()));
+                    arguments.push((meta_list.len() - 1, name));
+                }
+            }
+        }
+        for (dir, (cache, arguments)) in commit_arguments {
+            cache.read_arguments_last_commits(&dir, &mut meta_list, &arguments);
         }
         // Only calculate the total size of a directory if it will be displayed
-        if self.flags.total_size.0 && self.flags.blocks.displays_size() {
-            for meta in &mut meta_list.iter_mut() {
-                meta.calculate_total_size();
+        if self.flags.total_size.0
+            && self.flags.git_rev.0.is_none()
+            && (self.flags.blocks.displays_size() || self.flags.format.is_machine_readable())
+        {
+            let accumulator = SizeAccumulator::new(&self.flags);
+            if accumulator.parallel {
+                meta_list
+                    .par_iter_mut()
+                    .for_each(|meta| meta.calculate_total_size(&accumulator));
+            } else {
+                for meta in &mut meta_list.iter_mut() {
+                    meta.calculate_total_size(&accumulator);
+                }
+            }
+            accumulator.save();
+        }
+
+        if self.flags.predicates.is_active() {
+            meta_list.retain(|meta| meta.matches_argument_predicates(&self.flags));
+            for meta in &mut meta_list {
+                meta.retain_matching(&self.flags);
             }
         }
 
@@ -178,7 +311,15 @@ impl Core {
     }
 
     fn display(&self, metas: &[Meta]) {
-        let output = if self.flags.layout == Layout::Tree {
+        let output = if self.flags.format == FormatFlag::Json {
+            display::json(metas)
+        } else if self.flags.format == FormatFlag::Ndjson {
+            display::ndjson(metas)
+        } else if self.flags.format == FormatFlag::Csv {
+            display::delimited(metas, &self.flags, &self.git_theme, ',')
+        } else if self.flags.format == FormatFlag::Tsv {
+            display::delimited(metas, &self.flags, &self.git_theme, '\t')
+        } else if self.flags.layout == Layout::Tree {
             display::tree(
                 metas,
                 &self.flags,
@@ -198,4 +339,37 @@ impl Core {
 
         print_output!("{}", output);
     }
+
+    /// Display the duplicate files, with the group of each file in the machine readable formats.
+    fn display_duplicates(&self, groups: &[DuplicateGroup]) {
+        if !self.flags.format.is_machine_readable() {
+            let output = display::duplicates(
+                groups,
+                &self.flags,
+                &self.colors,
+                &self.icons,
+                &self.git_theme,
+            );
+            print_output!("{}", output);
+            return;
+        }
+
+        let output = match self.flags.format {
+            FormatFlag::Json => display::duplicates_json(groups),
+            FormatFlag::Ndjson => display::duplicates_ndjson(groups),
+            FormatFlag::Csv => {
+                display::duplicates_delimited(groups, &self.flags, &self.git_theme, ',')
+            }
+            _ => display::duplicates_delimited(groups, &self.flags, &self.git_theme, '\t'),
+        };
+        print_output!("{}", output);
+    }
+}
+
+/// The canonicalized parent directory of a path argument and its name in there, so that `.` or
+/// `sub/..` are looked up under their real name.
+fn argument_dir(path: &Path) -> Option<(PathBuf, String)> {
+    let path = std::fs::canonicalize(path).ok()?;
+    let name = path.file_name()?.to_str()?.to_string();
+    Some((path.parent()?.to_path_buf(), name))
 }
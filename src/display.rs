@@ -1,11 +1,15 @@
-use crate::color::{Colors, Elem};
+use crate::color::{Colors, Elem, ThemeOption};
+use crate::duplicates::DuplicateGroup;
 use crate::flags::blocks::Block;
-use crate::flags::{Display, Flags, HyperlinkOption, Layout};
+use crate::flags::format_template::{Align, Field, Segment};
+use crate::flags::{Blocks, Display, Flags, HyperlinkOption, Layout, SizeFlag, TimeField};
 use crate::git_theme::GitTheme;
 use crate::icon::Icons;
 use crate::meta::name::DisplayOption;
-use crate::meta::{FileType, Meta, OwnerCache};
+use crate::meta::{FileType, GitDiffStat, GitFileStatus, Meta, OwnerCache, Size};
+use serde::Serialize;
 use std::collections::HashMap;
+use std::path::Path;
 use term_grid::{Cell, Direction, Filling, Grid, GridOptions};
 use terminal_size::terminal_size;
 use unicode_width::UnicodeWidthStr;
@@ -51,16 +55,15 @@ pub fn tree(
     });
 
     let padding_rules = get_padding_rules(metas, flags);
-    let mut index = 0;
-    for (i, block) in flags.blocks.0.iter().enumerate() {
-        if block == &Block::Name {
-            index = i;
-            break;
-        }
-    }
-
     let owner_cache = OwnerCache::default();
 
+    // the summaries of the repositories come before the tree
+    let mut output: String = metas
+        .iter()
+        .filter_map(|meta| meta.repo_summary.as_ref())
+        .map(|summary| format!("{}\n", summary.render(colors, git_theme)))
+        .collect();
+
     for cell in inner_display_tree(
         metas,
         &owner_cache,
@@ -68,14 +71,520 @@ pub fn tree(
         colors,
         icons,
         git_theme,
+        &DisplayOption::FileName,
         (0, ""),
         &padding_rules,
-        index,
+        name_index(flags),
     ) {
         grid.add(cell);
     }
 
-    grid.fit_into_columns(flags.blocks.0.len()).to_string()
+    output += &grid.fit_into_columns(flags.blocks.0.len()).to_string();
+    output
+}
+
+/// Print each group of duplicates under a line with the size of its files and the space wasted
+/// by the copies, as a grid or a tree of their paths, and then the total wasted space.
+pub fn duplicates(
+    groups: &[DuplicateGroup],
+    flags: &Flags,
+    colors: &Colors,
+    icons: &Icons,
+    git_theme: &GitTheme,
+) -> String {
+    if groups.is_empty() {
+        return String::new();
+    }
+
+    let term_width = terminal_size().map(|(w, _)| w.0 as usize);
+    let owner_cache = OwnerCache::default();
+    let render_size = |bytes: u64| Size::with_allocated(bytes, bytes).render(colors, flags, None);
+
+    let mut output = String::new();
+    for group in groups {
+        let heading = format!(
+            "{} files of {}, {} wasted",
+            group.files.len(),
+            render_size(group.size()),
+            render_size(group.wasted())
+        );
+        if flags.layout == Layout::Tree {
+            let mut grid = Grid::new(GridOptions {
+                filling: Filling::Spaces(1),
+                direction: Direction::LeftToRight,
+            });
+            for cell in inner_display_tree(
+                &group.files,
+                &owner_cache,
+                flags,
+                colors,
+                icons,
+                git_theme,
+                &DisplayOption::None,
+                (1, ""),
+                &get_padding_rules(&group.files, flags),
+                name_index(flags),
+            ) {
+                grid.add(cell);
+            }
+            output += &format!("{heading}\n{}", grid.fit_into_columns(flags.blocks.0.len()));
+        } else {
+            output += &format!("{heading}:\n");
+            output += &inner_display_grid(
+                &DisplayOption::None,
+                &group.files,
+                &owner_cache,
+                flags,
+                colors,
+                icons,
+                git_theme,
+                1,
+                term_width,
+            );
+        }
+        output.push('\n');
+    }
+
+    let wasted: u64 = groups.iter().map(DuplicateGroup::wasted).sum();
+    output += &format!(
+        "{} group{} of duplicates, {} wasted\n",
+        groups.len(),
+        if groups.len() == 1 { "" } else { "s" },
+        render_size(wasted)
+    );
+    output
+}
+
+/// The records of the duplicate files, each one with the position of its group from 1.
+fn duplicate_records<'a>(
+    groups: &'a [DuplicateGroup],
+    owner_cache: &OwnerCache,
+) -> Vec<JsonDuplicate<'a>> {
+    groups
+        .iter()
+        .enumerate()
+        .flat_map(|(index, group)| {
+            group.files.iter().map(move |meta| JsonDuplicate {
+                group: index + 1,
+                meta: JsonMeta::new(meta, owner_cache, false),
+            })
+        })
+        .collect()
+}
+
+/// Serialize the duplicate files as a single JSON array, with the `group` of each file.
+pub fn duplicates_json(groups: &[DuplicateGroup]) -> String {
+    let owner_cache = OwnerCache::default();
+    let records = duplicate_records(groups, &owner_cache);
+    let mut output = serde_json::to_string_pretty(&records).expect("failed to serialize metas");
+    output.push('\n');
+    output
+}
+
+/// Serialize the duplicate files as one JSON object per line, with the `group` of each file.
+pub fn duplicates_ndjson(groups: &[DuplicateGroup]) -> String {
+    let owner_cache = OwnerCache::default();
+    duplicate_records(groups, &owner_cache)
+        .iter()
+        .map(|record| serde_json::to_string(record).expect("failed to serialize meta") + "\n")
+        .collect()
+}
+
+/// Print the duplicate files as delimited columns, the first one being the group of the file.
+pub fn duplicates_delimited(
+    groups: &[DuplicateGroup],
+    flags: &Flags,
+    git_theme: &GitTheme,
+    separator: char,
+) -> String {
+    let owner_cache = OwnerCache::default();
+    let headers: Vec<&str> = std::iter::once("Group")
+        .chain(flags.blocks.0.iter().map(Block::get_header))
+        .collect();
+    let mut output = delimited_line(headers, separator);
+
+    for (index, group) in groups.iter().enumerate() {
+        for meta in &group.files {
+            let name = meta.path.to_string_lossy().to_string();
+            output += &format!("{}{separator}", index + 1);
+            output += &delimited_row(meta, name, flags, git_theme, separator, &owner_cache);
+        }
+    }
+    output
+}
+
+/// The position of the name block, after which the tree edges are drawn.
+fn name_index(flags: &Flags) -> usize {
+    flags
+        .blocks
+        .0
+        .iter()
+        .position(|block| block == &Block::Name)
+        .unwrap_or(0)
+}
+
+/// Serialize the listing as a single JSON array, nesting directory contents under `content`.
+pub fn json(metas: &[Meta]) -> String {
+    let owner_cache = OwnerCache::default();
+    let entries: Vec<JsonMeta> = metas
+        .iter()
+        .map(|meta| JsonMeta::new(meta, &owner_cache, true))
+        .collect();
+
+    let mut output = serde_json::to_string_pretty(&entries).expect("failed to serialize metas");
+    output.push('\n');
+    output
+}
+
+/// Serialize the listing as one JSON object per line, walking directory contents depth first.
+pub fn ndjson(metas: &[Meta]) -> String {
+    fn inner(metas: &[Meta], owner_cache: &OwnerCache, output: &mut String) {
+        for meta in metas {
+            let entry = JsonMeta::new(meta, owner_cache, false);
+            *output += &serde_json::to_string(&entry).expect("failed to serialize meta");
+            output.push('\n');
+
+            if let Some(content) = &meta.content {
+                inner(content, owner_cache, output);
+            }
+        }
+    }
+
+    let owner_cache = OwnerCache::default();
+    let mut output = String::new();
+    inner(metas, &owner_cache, &mut output);
+    output
+}
+
+/// Print the selected blocks as delimited columns, with a header row of their names.
+///
+/// The values are raw and never colored: sizes in bytes, octal permissions and RFC 3339 dates.
+/// Like the grid, the directories given as arguments are replaced by their content, whose names
+/// are relative to them.
+pub fn delimited(metas: &[Meta], flags: &Flags, git_theme: &GitTheme, separator: char) -> String {
+    fn inner(
+        metas: &[Meta],
+        base_path: &Path,
+        flags: &Flags,
+        git_theme: &GitTheme,
+        separator: char,
+        owner_cache: &OwnerCache,
+        output: &mut String,
+    ) {
+        for meta in metas {
+            let name = match meta.path.strip_prefix(base_path) {
+                Ok(path) if meta.name.name != "." && meta.name.name != ".." => {
+                    path.to_string_lossy().to_string()
+                }
+                _ => meta.name.name.clone(),
+            };
+            *output += &delimited_row(meta, name, flags, git_theme, separator, owner_cache);
+
+            if let Some(content) = &meta.content {
+                inner(
+                    content,
+                    base_path,
+                    flags,
+                    git_theme,
+                    separator,
+                    owner_cache,
+                    output,
+                );
+            }
+        }
+    }
+
+    let owner_cache = OwnerCache::default();
+    let headers: Vec<&str> = flags.blocks.0.iter().map(Block::get_header).collect();
+    let mut output = delimited_line(headers, separator);
+
+    for meta in metas {
+        let is_dir = matches!(meta.file_type, FileType::Directory { .. })
+            || (matches!(meta.file_type, FileType::SymLink { is_dir: true })
+                && flags.blocks.0.len() == 1);
+        if !is_dir || flags.display == Display::DirectoryOnly {
+            let name = meta.path.to_string_lossy().to_string();
+            output += &delimited_row(meta, name, flags, git_theme, separator, &owner_cache);
+        }
+        if let Some(content) = &meta.content {
+            inner(
+                content,
+                &meta.path,
+                flags,
+                git_theme,
+                separator,
+                &owner_cache,
+                &mut output,
+            );
+        }
+    }
+    output
+}
+
+/// The raw values of the blocks of a [Meta], `name` being shown in the name block. The absent
+/// values are empty fields, rather than the placeholders of the long view.
+fn delimited_row(
+    meta: &Meta,
+    name: String,
+    flags: &Flags,
+    git_theme: &GitTheme,
+    separator: char,
+    owner_cache: &OwnerCache,
+) -> String {
+    let plain = Colors::new(ThemeOption::NoColor);
+    let values: Vec<String> = flags
+        .blocks
+        .0
+        .iter()
+        .map(|block| {
+            let value = match block {
+                Block::Permission => meta
+                    .permissions_or_attributes
+                    .as_ref()
+                    .and_then(|p| p.permissions())
+                    .map(|p| p.octal()),
+                Block::User => meta.owner.as_ref().map(|o| o.user_name(owner_cache)),
+                Block::Group => meta.owner.as_ref().map(|o| o.group_name(owner_cache)),
+                Block::Context => meta.access_control.as_ref().and_then(|ac| ac.context()),
+                Block::Size | Block::SizeValue => {
+                    meta.size.map(|size| size.get(flags.size_mode).to_string())
+                }
+                Block::Date => meta.date(flags.time).and_then(|date| date.to_rfc3339()),
+                Block::Time(time) => meta.date(*time).and_then(|date| date.to_rfc3339()),
+                Block::Name => Some(name.clone()),
+                Block::INode => meta
+                    .inode
+                    .and_then(|inode| inode.index())
+                    .map(|i| i.to_string()),
+                Block::Links => meta
+                    .links
+                    .and_then(|links| links.count())
+                    .map(|c| c.to_string()),
+                Block::Xattrs => meta
+                    .access_control
+                    .as_ref()
+                    .map(|ac| ac.xattrs().len().to_string()),
+                Block::Acl => meta
+                    .access_control
+                    .as_ref()
+                    .and_then(|ac| ac.short_acl(owner_cache)),
+                Block::Capabilities => meta
+                    .access_control
+                    .as_ref()
+                    .and_then(|ac| ac.capabilities()),
+                Block::InodeFlags => meta.inode_flags.map(|flags| flags.letters()),
+                Block::Hash => meta
+                    .content_hash
+                    .as_ref()
+                    .map(|hash| hash.as_str().to_string()),
+                Block::Mime => meta.name.mime().map(|mime| mime.essence().to_string()),
+                Block::Entries => meta.entries.map(|entries| entries.count().to_string()),
+                Block::GitStatus => meta
+                    .git_status
+                    .filter(|status| *status != GitFileStatus::default())
+                    .map(|status| status.render(&plain, git_theme).content().clone()),
+                Block::GitCommit => meta.git_commit.as_ref().map(|c| c.id().to_string()),
+                Block::GitAuthor => meta.git_commit.as_ref().map(|c| c.author().to_string()),
+                Block::GitDate => meta.git_commit.as_ref().and_then(|c| c.date().to_rfc3339()),
+                Block::GitDiff => meta.git_diff.map(|stat| stat.to_string()),
+            };
+            value.unwrap_or_default()
+        })
+        .collect();
+    delimited_line(values, separator)
+}
+
+/// Join the values with the separator. CSV values are quoted when needed, while tabs, newlines
+/// and backslashes are escaped in TSV, which cannot quote them.
+fn delimited_line<T: AsRef<str>>(values: Vec<T>, separator: char) -> String {
+    let mut line = values
+        .iter()
+        .map(|value| {
+            let value = value.as_ref();
+            if separator == '\t' {
+                value
+                    .replace('\\', "\\\\")
+                    .replace('\t', "\\t")
+                    .replace('\n', "\\n")
+                    .replace('\r', "\\r")
+            } else if value.contains([separator, '"', '\n', '\r']) {
+                format!("\"{}\"", value.replace('"', "\"\""))
+            } else {
+                value.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(&separator.to_string());
+    line.push('\n');
+    line
+}
+
+/// The machine readable representation of a [Meta].
+///
+/// Every value is raw and uncolored, and the fields do not depend on the display flags. The
+/// values read on demand, `inode_flags`, `hash`, `mime`, `entries` and the Git ones, are only
+/// filled when their block is selected and serialize as `null` otherwise.
+#[derive(Serialize)]
+struct JsonMeta<'a> {
+    name: &'a str,
+    path: String,
+    file_type: &'static str,
+    size: Option<u64>,
+    allocated_size: Option<u64>,
+    permissions: Option<JsonPermissions>,
+    owner: Option<JsonOwner>,
+    date: Option<String>,
+    accessed: Option<String>,
+    changed: Option<String>,
+    created: Option<String>,
+    inode: Option<u64>,
+    links: Option<u64>,
+    inode_flags: Option<String>,
+    hash: Option<String>,
+    mime: Option<&'static str>,
+    entries: Option<u64>,
+    git_status: Option<GitFileStatus>,
+    git_commit: Option<JsonGitCommit<'a>>,
+    git_diff: Option<GitDiffStat>,
+    access_control: Option<JsonAccessControl<'a>>,
+    symlink_target: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<Vec<JsonMeta<'a>>>,
+}
+
+/// A duplicate file and the position of its group.
+#[derive(Serialize)]
+struct JsonDuplicate<'a> {
+    group: usize,
+    #[serde(flatten)]
+    meta: JsonMeta<'a>,
+}
+
+#[derive(Serialize)]
+struct JsonPermissions {
+    rwx: String,
+    octal: String,
+}
+
+#[derive(Serialize)]
+struct JsonOwner {
+    uid: Option<u32>,
+    gid: Option<u32>,
+    user: String,
+    group: String,
+}
+
+#[derive(Serialize)]
+struct JsonGitCommit<'a> {
+    id: &'a str,
+    author: &'a str,
+    date: Option<String>,
+}
+
+#[derive(Serialize)]
+struct JsonAccessControl<'a> {
+    acl: bool,
+    selinux_context: Option<&'a str>,
+    smack_context: Option<&'a str>,
+    acl_entries: Vec<String>,
+    capabilities: Option<String>,
+    xattrs: Vec<JsonXattr<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonXattr<'a> {
+    name: &'a str,
+    size: usize,
+}
+
+impl<'a> JsonMeta<'a> {
+    fn new(meta: &'a Meta, owner_cache: &OwnerCache, nested: bool) -> Self {
+        let non_empty = |s: &'a str| if s.is_empty() { None } else { Some(s) };
+
+        Self {
+            name: &meta.name.name,
+            path: meta.path.to_string_lossy().to_string(),
+            file_type: meta.file_type.name(),
+            size: meta.size.as_ref().map(|size| size.get_bytes()),
+            allocated_size: meta.size.as_ref().map(|size| size.get_allocated()),
+            permissions: meta
+                .permissions_or_attributes
+                .as_ref()
+                .and_then(|p| p.permissions())
+                .map(|p| JsonPermissions {
+                    rwx: p.rwx(),
+                    octal: p.octal(),
+                }),
+            owner: meta.owner.as_ref().map(|owner| JsonOwner {
+                #[cfg(unix)]
+                uid: Some(owner.uid()),
+                #[cfg(not(unix))]
+                uid: None,
+                #[cfg(unix)]
+                gid: Some(owner.gid()),
+                #[cfg(not(unix))]
+                gid: None,
+                user: owner.user_name(owner_cache),
+                group: owner.group_name(owner_cache),
+            }),
+            date: meta
+                .date(TimeField::Modified)
+                .and_then(|date| date.to_rfc3339()),
+            accessed: meta
+                .date(TimeField::Accessed)
+                .and_then(|date| date.to_rfc3339()),
+            changed: meta
+                .date(TimeField::Changed)
+                .and_then(|date| date.to_rfc3339()),
+            created: meta
+                .date(TimeField::Birth)
+                .and_then(|date| date.to_rfc3339()),
+            inode: meta.inode.and_then(|inode| inode.index()),
+            links: meta.links.and_then(|links| links.count()),
+            inode_flags: meta.inode_flags.map(|inode_flags| inode_flags.letters()),
+            hash: meta
+                .content_hash
+                .as_ref()
+                .map(|hash| hash.as_str().to_string()),
+            mime: meta.name.mime().map(|mime| mime.essence()),
+            entries: meta.entries.map(|entries| entries.count()),
+            git_status: meta.git_status,
+            git_commit: meta.git_commit.as_ref().map(|commit| JsonGitCommit {
+                id: commit.id(),
+                author: commit.author(),
+                date: commit.date().to_rfc3339(),
+            }),
+            git_diff: meta.git_diff,
+            access_control: meta
+                .access_control
+                .as_ref()
+                .map(|access_control| JsonAccessControl {
+                    acl: access_control.has_acl(),
+                    selinux_context: non_empty(access_control.selinux_context()),
+                    smack_context: non_empty(access_control.smack_context()),
+                    acl_entries: access_control.acl_entries(owner_cache),
+                    capabilities: access_control.capabilities(),
+                    xattrs: access_control
+                        .xattrs()
+                        .iter()
+                        .map(|xattr| JsonXattr {
+                            name: &xattr.name,
+                            size: xattr.size,
+                        })
+                        .collect(),
+                }),
+            symlink_target: meta.symlink.symlink_string(),
+            content: match (&meta.content, nested) {
+                (Some(content), true) => Some(
+                    content
+                        .iter()
+                        .map(|meta| JsonMeta::new(meta, owner_cache, true))
+                        .collect(),
+                ),
+                _ => None,
+            },
+        }
+    }
 }
 
 #[allow(clippy::too_many_arguments)] // should wrap flags, colors, icons, git_theme into one struct
@@ -92,8 +601,16 @@ fn inner_display_grid(
 ) -> String {
     let mut output = String::new();
     let mut cells = Vec::new();
+    let mut displayed = Vec::new();
 
     let padding_rules = get_padding_rules(metas, flags);
+    let template = flags.layout == Layout::OneLine && flags.format_template.is_active();
+    let columns = if template {
+        template_columns(metas, flags)
+    } else {
+        Vec::new()
+    };
+    let mut rows = Vec::new();
     let mut grid = match flags.layout {
         Layout::OneLine => Grid::new(GridOptions {
             filling: Filling::Spaces(1),
@@ -121,6 +638,30 @@ fn inner_display_grid(
             continue;
         }
 
+        if template {
+            rows.push(
+                columns
+                    .iter()
+                    .map(|(column_flags, padding_rules)| {
+                        get_output(
+                            meta,
+                            owner_cache,
+                            colors,
+                            icons,
+                            git_theme,
+                            column_flags,
+                            display_option,
+                            padding_rules,
+                            (0, ""),
+                        )
+                        .concat()
+                    })
+                    .collect(),
+            );
+            displayed.push(meta);
+            continue;
+        }
+
         let blocks = get_output(
             meta,
             owner_cache,
@@ -139,10 +680,12 @@ fn inner_display_grid(
                 contents: block,
             });
         }
+        displayed.push(meta);
     }
 
     // Print block headers
-    if flags.header.0 && flags.layout == Layout::OneLine && !cells.is_empty() {
+    let header = flags.header.0 && flags.layout == Layout::OneLine && !cells.is_empty();
+    if header {
         add_header(flags, &cells, &mut grid);
     }
 
@@ -150,7 +693,16 @@ fn inner_display_grid(
         grid.add(cell);
     }
 
-    if flags.layout == Layout::Grid {
+    if template {
+        let header = flags.header.0 && !rows.is_empty();
+        let lines = render_template(&rows, header, flags);
+        if flags.extended_attributes.0 || flags.list_acl.0 {
+            let header_rows = usize::from(header);
+            output += &insert_details(&lines, &displayed, header_rows, flags, colors, owner_cache);
+        } else {
+            output += &lines;
+        }
+    } else if flags.layout == Layout::Grid {
         if let Some(tw) = term_width {
             if let Some(gridded_output) = grid.fit_into_width(tw) {
                 output += &gridded_output.to_string();
@@ -164,7 +716,14 @@ fn inner_display_grid(
             output += &grid.fit_into_columns(1).to_string();
         }
     } else {
-        output += &grid.fit_into_columns(flags.blocks.0.len()).to_string();
+        let rows = grid.fit_into_columns(flags.blocks.0.len()).to_string();
+        let details = flags.extended_attributes.0 || flags.list_acl.0;
+        if details && flags.layout == Layout::OneLine {
+            let header_rows = usize::from(header);
+            output += &insert_details(&rows, &displayed, header_rows, flags, colors, owner_cache);
+        } else {
+            output += &rows;
+        }
     }
 
     let should_display_folder_path = should_display_folder_path(depth, metas);
@@ -175,6 +734,9 @@ fn inner_display_grid(
             if should_display_folder_path {
                 output += &display_folder_path(meta);
             }
+            if let Some(summary) = &meta.repo_summary {
+                output += &format!("{}\n", summary.render(colors, git_theme));
+            }
 
             let display_option = DisplayOption::Relative {
                 base_path: &meta.path,
@@ -228,6 +790,107 @@ fn add_header(flags: &Flags, cells: &[Cell], grid: &mut Grid) {
     }
 }
 
+/// The flags rendering each field of the format template alone, with the size or date format
+/// of the field, and their padding rules.
+fn template_columns(metas: &[Meta], flags: &Flags) -> Vec<(Flags, HashMap<Block, usize>)> {
+    flags
+        .format_template
+        .fields()
+        .zip(&flags.blocks.0)
+        .map(|(field, block)| {
+            let mut column_flags = flags.clone();
+            column_flags.blocks = Blocks(vec![*block]);
+            if let Some(size) = field.size {
+                column_flags.size = size;
+            }
+            // sizes in bytes have no unit, only the value is rendered to avoid a trailing space
+            if *block == Block::Size
+                && column_flags.size == SizeFlag::Bytes
+                && !column_flags.dir_count.0
+            {
+                column_flags.blocks = Blocks(vec![Block::SizeValue]);
+            }
+            if let Some(date) = &field.date {
+                column_flags.date = date.clone();
+            }
+            let padding_rules = get_padding_rules(metas, &column_flags);
+            (column_flags, padding_rules)
+        })
+        .collect()
+}
+
+/// Join the rendered fields of each row with the literal text of the format template. Each
+/// column is as wide as its widest value, or as the width of its field if wider.
+fn render_template(rows: &[Vec<String>], header: bool, flags: &Flags) -> String {
+    let hyperlink = flags.hyperlink == HyperlinkOption::Always;
+    let fields: Vec<&Field> = flags.format_template.fields().collect();
+
+    let mut widths: Vec<usize> = fields
+        .iter()
+        .zip(&flags.blocks.0)
+        .map(|(field, block)| {
+            let header_width = if header {
+                get_visible_width(block.get_header(), hyperlink)
+            } else {
+                0
+            };
+            field.width.unwrap_or(0).max(header_width)
+        })
+        .collect();
+    for row in rows {
+        for (width, value) in widths.iter_mut().zip(row) {
+            *width = (*width).max(get_visible_width(value, hyperlink));
+        }
+    }
+
+    let header_row: Vec<String> = flags
+        .blocks
+        .0
+        .iter()
+        .zip(&widths)
+        .map(|(block, width)| {
+            crossterm::style::Stylize::attribute(
+                format!("{: ^1$}", block.get_header(), width),
+                crossterm::style::Attribute::Underlined,
+            )
+            .to_string()
+        })
+        .collect();
+
+    // a trailing field aligned to the left is not padded, like the name in the long view
+    let last_field = match flags.format_template.0.last() {
+        Some(Segment::Field(field)) if field.align == Align::Left => fields.len(),
+        _ => usize::MAX,
+    };
+
+    let mut output = String::new();
+    for row in header.then_some(&header_row).into_iter().chain(rows) {
+        let mut values = row.iter().zip(&widths).zip(&fields).enumerate();
+        for segment in &flags.format_template.0 {
+            match segment {
+                Segment::Literal(text) => output += text,
+                Segment::Field(_) => {
+                    let Some((index, ((value, width), field))) = values.next() else {
+                        continue;
+                    };
+                    let padding = width.saturating_sub(get_visible_width(value, hyperlink));
+                    let (left, right) = match field.align {
+                        _ if index + 1 == last_field => (0, 0),
+                        Align::Left => (0, padding),
+                        Align::Right => (padding, 0),
+                        Align::Center => (padding / 2, padding - padding / 2),
+                    };
+                    output += &" ".repeat(left);
+                    output += value;
+                    output += &" ".repeat(right);
+                }
+            }
+        }
+        output.push('\n');
+    }
+    output
+}
+
 #[allow(clippy::too_many_arguments)]
 fn inner_display_tree(
     metas: &[Meta],
@@ -236,6 +899,7 @@ fn inner_display_tree(
     colors: &Colors,
     icons: &Icons,
     git_theme: &GitTheme,
+    display_option: &DisplayOption,
     tree_depth_prefix: (usize, &str),
     padding_rules: &HashMap<Block, usize>,
     tree_index: usize,
@@ -262,7 +926,7 @@ fn inner_display_tree(
             icons,
             git_theme,
             flags,
-            &DisplayOption::FileName,
+            display_option,
             padding_rules,
             (tree_index, &current_prefix),
         ) {
@@ -291,6 +955,7 @@ fn inner_display_tree(
                 colors,
                 icons,
                 git_theme,
+                &DisplayOption::FileName,
                 (tree_depth_prefix.0 + 1, &new_prefix),
                 padding_rules,
                 tree_index,
@@ -321,6 +986,37 @@ fn display_folder_path(meta: &Meta) -> String {
     format!("\n{}:\n", meta.path.to_string_lossy())
 }
 
+/// Insert the extended attributes and the ACL entries of each entry under its row, skipping
+/// the `header_rows`.
+fn insert_details(
+    rows: &str,
+    metas: &[&Meta],
+    header_rows: usize,
+    flags: &Flags,
+    colors: &Colors,
+    owner_cache: &OwnerCache,
+) -> String {
+    let mut output = String::with_capacity(rows.len());
+    let mut lines = rows.lines();
+    for line in lines.by_ref().take(header_rows) {
+        output += line;
+        output.push('\n');
+    }
+    for (line, meta) in lines.zip(metas) {
+        output += line;
+        output.push('\n');
+        if let Some(access_control) = &meta.access_control {
+            if flags.extended_attributes.0 {
+                output += &access_control.render_xattrs(colors);
+            }
+            if flags.list_acl.0 {
+                output += &access_control.render_acl_entries(colors, owner_cache);
+            }
+        }
+    }
+    output
+}
+
 #[allow(clippy::too_many_arguments)]
 fn get_output(
     meta: &Meta,
@@ -379,22 +1075,77 @@ fn get_output(
                 Some(access_control) => access_control.render_context(colors),
                 None => colorize_missing("?"),
             }),
+            Block::Xattrs => block_vec.push(match &meta.access_control {
+                Some(access_control) => access_control.render_xattrs_count(colors),
+                None => colorize_missing("?"),
+            }),
+            Block::Acl => block_vec.push(match &meta.access_control {
+                Some(access_control) => access_control.render_acl(colors, owner_cache),
+                None => colorize_missing("?"),
+            }),
+            Block::Capabilities => block_vec.push(match &meta.access_control {
+                Some(access_control) => access_control.render_capabilities(colors),
+                None => colorize_missing("?"),
+            }),
+            // only regular files and directories on supporting filesystems carry inode flags
+            Block::InodeFlags => block_vec.push(match &meta.inode_flags {
+                Some(inode_flags) => inode_flags.render(colors),
+                None => colorize_missing("-"),
+            }),
+            Block::Entries => block_vec.push(match &meta.entries {
+                Some(entries) => entries.render(colors),
+                None => colorize_missing("-"),
+            }),
+            Block::Mime => block_vec.push(match meta.name.mime() {
+                Some(mime) => mime.render(colors),
+                None => colorize_missing("-"),
+            }),
+            // untracked files and those outside of a repository have no commit
+            Block::GitCommit => block_vec.push(match &meta.git_commit {
+                Some(commit) => commit.render_id(colors),
+                None => colorize_missing("-"),
+            }),
+            Block::GitAuthor => block_vec.push(match &meta.git_commit {
+                Some(commit) => commit.render_author(colors),
+                None => colorize_missing("-"),
+            }),
+            Block::GitDate => block_vec.push(match &meta.git_commit {
+                Some(commit) => commit.render_date(colors),
+                None => colorize_missing("-"),
+            }),
+            // unchanged and untracked files have no diff
+            Block::GitDiff => block_vec.push(match &meta.git_diff {
+                Some(stat) => stat.render(colors),
+                None => colorize_missing("-"),
+            }),
+            // directories, special files and files above the maximum size are not hashed
+            Block::Hash => block_vec.push(match &meta.content_hash {
+                Some(hash) => hash.render(colors),
+                None => colorize_missing("-"),
+            }),
             Block::Size => {
                 let pad = if Layout::Tree == flags.layout && 0 == tree.0 && 0 == i {
                     None
                 } else {
                     Some(padding_rules[&Block::SizeValue])
                 };
-                block_vec.push(match &meta.size {
-                    Some(size) => size.render(colors, flags, pad),
-                    None => colorize_missing("?"),
+                block_vec.push(match (&meta.size, meta.entries) {
+                    (_, Some(entries)) if flags.dir_count.0 => {
+                        entries.render_as_size(colors, flags, pad)
+                    }
+                    (Some(size), _) => size.render(colors, flags, pad),
+                    (None, _) => colorize_missing("?"),
                 })
             }
             Block::SizeValue => block_vec.push(match &meta.size {
                 Some(size) => size.render_value(colors, flags),
                 None => colorize_missing("?"),
             }),
-            Block::Date => block_vec.push(match &meta.date {
+            Block::Date => block_vec.push(match meta.date(flags.time) {
+                Some(date) => date.render(colors, flags),
+                None => colorize_missing("?"),
+            }),
+            Block::Time(time) => block_vec.push(match meta.date(*time) {
                 Some(date) => date.render(colors, flags),
                 None => colorize_missing("?"),
             }),
@@ -464,9 +1215,10 @@ fn detect_size_lengths(metas: &[Meta], flags: &Flags) -> usize {
     let mut max_value_length: usize = 0;
 
     for meta in metas {
-        let value_len = match &meta.size {
-            Some(size) => size.value_string(flags).len(),
-            None => 0,
+        let value_len = match (&meta.size, meta.entries) {
+            (_, Some(entries)) if flags.dir_count.0 => entries.count().to_string().len(),
+            (Some(size), _) => size.value_string(flags).len(),
+            (None, _) => 0,
         };
 
         if value_len > max_value_length {
@@ -704,7 +1456,7 @@ mod tests {
         dir.child("one.d/.hidden").touch().unwrap();
         let mut metas = Meta::from_path(Path::new(dir.path()), false, PermissionFlag::Rwx)
             .unwrap()
-            .recurse_into(42, &flags, None)
+            .recurse_into(42, &flags, None, None)
             .unwrap()
             .0
             .unwrap();
@@ -737,7 +1489,7 @@ mod tests {
         dir.child("dir/file").touch().unwrap();
         let metas = Meta::from_path(Path::new(dir.path()), false, PermissionFlag::Rwx)
             .unwrap()
-            .recurse_into(42, &flags, None)
+            .recurse_into(42, &flags, None, None)
             .unwrap()
             .0
             .unwrap();
@@ -778,7 +1530,7 @@ mod tests {
         dir.child("dir/file").touch().unwrap();
         let metas = Meta::from_path(Path::new(dir.path()), false, PermissionFlag::Rwx)
             .unwrap()
-            .recurse_into(42, &flags, None)
+            .recurse_into(42, &flags, None, None)
             .unwrap()
             .0
             .unwrap();
@@ -818,7 +1570,7 @@ mod tests {
         dir.child("one.d/two").touch().unwrap();
         let metas = Meta::from_path(Path::new(dir.path()), false, PermissionFlag::Rwx)
             .unwrap()
-            .recurse_into(42, &flags, None)
+            .recurse_into(42, &flags, None, None)
             .unwrap()
             .0
             .unwrap();
@@ -849,7 +1601,7 @@ mod tests {
         dir.child("test").touch().unwrap();
         let metas = Meta::from_path(Path::new(dir.path()), false, PermissionFlag::Rwx)
             .unwrap()
-            .recurse_into(1, &flags, None)
+            .recurse_into(1, &flags, None, None)
             .unwrap()
             .0
             .unwrap();
@@ -883,7 +1635,7 @@ mod tests {
         dir.child("testdir").create_dir_all().unwrap();
         let metas = Meta::from_path(Path::new(dir.path()), false, PermissionFlag::Rwx)
             .unwrap()
-            .recurse_into(1, &flags, None)
+            .recurse_into(1, &flags, None, None)
             .unwrap()
             .0
             .unwrap();
@@ -905,6 +1657,119 @@ mod tests {
         assert!(!output.contains("Name"));
     }
 
+    #[test]
+    fn test_json_nests_content() {
+        let argv = ["lsd", "--tree", "--format", "json"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("one.d").create_dir_all().unwrap();
+        dir.child("one.d/two").write_str("12345").unwrap();
+        let metas = Meta::from_path(Path::new(dir.path()), false, PermissionFlag::Rwx)
+            .unwrap()
+            .recurse_into(42, &flags, None, None)
+            .unwrap()
+            .0
+            .unwrap();
+
+        let value: serde_json::Value = serde_json::from_str(&json(&metas)).unwrap();
+        assert_eq!(value[0]["name"], "one.d");
+        assert_eq!(value[0]["file_type"], "directory");
+        assert_eq!(value[0]["content"][0]["name"], "two");
+        assert_eq!(value[0]["content"][0]["file_type"], "file");
+        assert_eq!(value[0]["content"][0]["size"], 5);
+        assert!(value[0]["content"][0].get("content").is_none());
+    }
+
+    #[test]
+    fn test_ndjson_one_entry_per_line() {
+        let argv = ["lsd", "--tree", "--format", "ndjson"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("one.d").create_dir_all().unwrap();
+        dir.child("one.d/two").touch().unwrap();
+        dir.child("three").touch().unwrap();
+        let mut metas = Meta::from_path(Path::new(dir.path()), false, PermissionFlag::Rwx)
+            .unwrap()
+            .recurse_into(42, &flags, None, None)
+            .unwrap()
+            .0
+            .unwrap();
+        sort(&mut metas, &sort::assemble_sorters(&flags));
+
+        let names: Vec<String> = ndjson(&metas)
+            .lines()
+            .map(|line| {
+                let value: serde_json::Value = serde_json::from_str(line).unwrap();
+                assert!(value.get("content").is_none());
+                value["name"].as_str().unwrap().to_string()
+            })
+            .collect();
+        assert_eq!(names, ["one.d", "two", "three"]);
+    }
+
+    #[test]
+    fn test_delimited_line_escapes_values() {
+        assert_eq!(
+            "plain,\"a,b\",\"say \"\"hi\"\"\",\"two\nlines\"\n",
+            delimited_line(vec!["plain", "a,b", "say \"hi\"", "two\nlines"], ',')
+        );
+        assert_eq!(
+            "a,b\tin\\ttab\ttwo\\nlines\tback\\\\slash\n",
+            delimited_line(vec!["a,b", "in\ttab", "two\nlines", "back\\slash"], '\t')
+        );
+    }
+
+    #[test]
+    fn test_delimited_relative_names() {
+        let argv = [
+            "lsd",
+            "--recursive",
+            "--format",
+            "csv",
+            "--blocks",
+            "size,name",
+        ];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("one.d/two").write_binary(&[0u8; 3]).unwrap();
+        dir.child("three").write_binary(&[0u8; 5]).unwrap();
+        let mut meta = Meta::from_path(Path::new(dir.path()), false, PermissionFlag::Rwx).unwrap();
+        meta.content = meta.recurse_into(42, &flags, None, None).unwrap().0;
+        let mut metas = vec![meta];
+        sort(&mut metas, &sort::assemble_sorters(&flags));
+
+        let output = delimited(&metas, &flags, &GitTheme::new(), ',');
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!("Size,Name", lines[0]);
+        assert!(lines[1].ends_with(",one.d"));
+        assert_eq!(["3,one.d/two", "5,three"], lines[2..]);
+    }
+
+    #[test]
+    fn test_delimited_absent_values() {
+        let argv = ["lsd", "--format", "csv", "--blocks", "acl,git,name"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("file").touch().unwrap();
+        let mut meta =
+            Meta::from_path(&dir.path().join("file"), false, PermissionFlag::Rwx).unwrap();
+        meta.git_status = Some(GitFileStatus::default());
+
+        let output = delimited(&[meta], &flags, &GitTheme::new(), ',');
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!("ACL,Git,Name", lines[0]);
+        assert!(lines[1].starts_with(",,"), "{}", lines[1]);
+        assert!(lines[1].ends_with("file"), "{}", lines[1]);
+    }
+
     #[test]
     fn test_folder_path() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
@@ -930,10 +1795,13 @@ mod tests {
         const NO: bool = false;
 
         assert_eq!(
-            should_display_folder_path(0, &[file.clone()]),
+            should_display_folder_path(0, std::slice::from_ref(&file)),
             YES // doesn't matter since there is no folder
         );
-        assert_eq!(should_display_folder_path(0, &[dir.clone()]), NO);
+        assert_eq!(
+            should_display_folder_path(0, std::slice::from_ref(&dir)),
+            NO
+        );
         assert_eq!(
             should_display_folder_path(0, &[file.clone(), dir.clone()]),
             YES
@@ -971,7 +1839,10 @@ mod tests {
         const YES: bool = true;
         const NO: bool = false;
 
-        assert_eq!(should_display_folder_path(0, &[link.clone()]), NO);
+        assert_eq!(
+            should_display_folder_path(0, std::slice::from_ref(&link)),
+            NO
+        );
 
         assert_eq!(
             should_display_folder_path(0, &[file.clone(), link.clone()]),
@@ -0,0 +1,177 @@
+//! Find the regular files of a listing that have the same content.
+//!
+//! The files are first grouped by size, and only the files sharing their size with another one
+//! are hashed, so that most files are never read.
+
+use crate::flags::{Flags, HashFlag, hash::HashAlgorithm};
+use crate::meta::{ContentHash, FileType, Meta};
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+/// Files with the same size and content digest.
+#[derive(Debug)]
+pub struct DuplicateGroup {
+    pub files: Vec<Meta>,
+    /// The cryptographic digest of the content of the files.
+    pub digest: ContentHash,
+}
+
+impl DuplicateGroup {
+    /// The size of each of the files.
+    pub fn size(&self) -> u64 {
+        self.files[0].size.map_or(0, |size| size.get_bytes())
+    }
+
+    /// The space used by the copies, i.e. all but one of the files.
+    pub fn wasted(&self) -> u64 {
+        self.size() * (self.files.len() as u64 - 1)
+    }
+}
+
+/// Group the regular files found in `metas` and their content by identical content, the groups
+/// wasting the most space first.
+///
+/// Empty files are left out, and so are the hard links to a file that is already listed, as they
+/// do not take more space. The whole files are hashed, whatever the maximum size of the hash
+/// block, and with SHA-256 when the hash block uses CRC32, whose collisions are too likely to
+/// tell two files apart.
+pub fn find(metas: &[Meta], flags: &Flags) -> Vec<DuplicateGroup> {
+    let mut by_size: HashMap<u64, Vec<&Meta>> = HashMap::new();
+    let mut inodes = HashSet::new();
+    collect_files(metas, &mut by_size, &mut inodes);
+
+    let hash_flag = HashFlag {
+        algorithm: match flags.hash.algorithm {
+            HashAlgorithm::Crc32 => HashAlgorithm::Sha256,
+            algorithm => algorithm,
+        },
+        max_size: u64::MAX,
+    };
+    let reuse_hash = hash_flag.algorithm == flags.hash.algorithm;
+    let candidates: Vec<(u64, &Meta)> = by_size
+        .into_iter()
+        .filter(|(_, files)| files.len() > 1)
+        .flat_map(|(size, files)| files.into_iter().map(move |meta| (size, meta)))
+        .collect();
+    let hash = |&(size, meta): &(u64, &Meta)| {
+        let digest = meta
+            .content_hash
+            .clone()
+            .filter(|_| reuse_hash)
+            .or_else(|| ContentHash::for_path(&meta.path, meta.file_type, size, hash_flag))?;
+        Some(((size, digest), meta.clone()))
+    };
+    // reading the files is most of the work, they are hashed by the threads of the pool
+    let hashed: Vec<_> = if flags.threads.is_parallel() {
+        candidates.par_iter().filter_map(hash).collect()
+    } else {
+        candidates.iter().filter_map(hash).collect()
+    };
+
+    let mut by_hash: HashMap<(u64, ContentHash), Vec<Meta>> = HashMap::new();
+    for (key, meta) in hashed {
+        by_hash.entry(key).or_default().push(meta);
+    }
+    let mut groups: Vec<DuplicateGroup> = by_hash
+        .into_iter()
+        .filter(|(_, files)| files.len() > 1)
+        .map(|((_, digest), files)| DuplicateGroup { files, digest })
+        .collect();
+
+    groups.sort_by(|a, b| {
+        b.wasted()
+            .cmp(&a.wasted())
+            .then_with(|| a.digest.cmp(&b.digest))
+    });
+    groups
+}
+
+fn collect_files<'a>(
+    metas: &'a [Meta],
+    by_size: &mut HashMap<u64, Vec<&'a Meta>>,
+    inodes: &mut HashSet<(u64, u64)>,
+) {
+    for meta in metas {
+        if let Some(content) = &meta.content {
+            collect_files(content, by_size, inodes);
+            continue;
+        }
+        if !matches!(meta.file_type, FileType::File { .. }) {
+            continue;
+        }
+        let size = meta.size.map_or(0, |size| size.get_bytes());
+        if size == 0 {
+            continue;
+        }
+        let inode = meta
+            .inode
+            .and_then(|inode| inode.device().zip(inode.index()));
+        if inode.is_some_and(|inode| !inodes.insert(inode)) {
+            continue;
+        }
+        by_size.entry(size).or_default().push(meta);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::find;
+    use crate::flags::hash::HashAlgorithm;
+    use crate::flags::{Flags, PermissionFlag};
+    use crate::meta::{ContentHash, Meta};
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_find_duplicates() {
+        let tmp = tempdir().unwrap();
+        fs::create_dir(tmp.path().join("sub")).unwrap();
+        for (name, content) in [
+            ("a", "same"),
+            ("sub/b", "same"),
+            ("c", "diff"),
+            ("d", "other content"),
+            ("e", ""),
+            ("f", ""),
+        ] {
+            fs::write(tmp.path().join(name), content).unwrap();
+        }
+        fs::hard_link(tmp.path().join("a"), tmp.path().join("link")).unwrap();
+
+        let flags = Flags::default();
+        let mut meta = Meta::from_path(tmp.path(), false, PermissionFlag::Rwx).unwrap();
+        meta.content = meta.recurse_into(usize::MAX, &flags, None, None).unwrap().0;
+
+        let groups = find(&[meta], &flags);
+        assert_eq!(1, groups.len());
+        assert_eq!(4, groups[0].size());
+        assert_eq!(4, groups[0].wasted());
+        let mut names: Vec<&str> = groups[0]
+            .files
+            .iter()
+            .map(|meta| meta.name.file_name())
+            .collect();
+        names.sort_unstable();
+        assert!(names == ["a", "b"] || names == ["b", "link"], "{names:?}");
+    }
+
+    #[test]
+    fn test_crc32_collision_is_not_duplicate() {
+        let tmp = tempdir().unwrap();
+        // two contents with the same CRC32
+        fs::write(tmp.path().join("a"), "plumless").unwrap();
+        fs::write(tmp.path().join("b"), "buckeroo").unwrap();
+
+        let mut flags = Flags::default();
+        flags.hash.algorithm = HashAlgorithm::Crc32;
+        let mut meta = Meta::from_path(tmp.path(), false, PermissionFlag::Rwx).unwrap();
+        meta.content = meta.recurse_into(usize::MAX, &flags, None, None).unwrap().0;
+        let content = meta.content.as_mut().unwrap();
+        for file in content.iter_mut() {
+            file.content_hash = ContentHash::for_path(&file.path, file.file_type, 8, flags.hash);
+        }
+        assert_eq!(content[0].content_hash, content[1].content_hash);
+
+        assert!(find(&[meta], &flags).is_empty());
+    }
+}
@@ -2,54 +2,101 @@ pub mod blocks;
 pub mod color;
 pub mod date;
 pub mod dereference;
+pub mod dir_count;
 pub mod display;
+pub mod duplicates;
+pub mod extended_attributes;
+pub mod extensions;
+pub mod format;
+pub mod format_template;
+pub mod git_dim_ignored;
+pub mod git_hide_ignored;
+pub mod git_rev;
+pub mod git_summary;
+pub mod gitignore;
+pub mod hash;
 pub mod header;
 pub mod hyperlink;
 pub mod icons;
 pub mod ignore_globs;
+pub mod include_globs;
 pub mod indicators;
 pub mod layout;
+pub mod list_acl;
 pub mod literal;
+pub mod mountpoint_indicator;
+pub mod one_file_system;
 pub mod permission;
+pub mod predicates;
 pub mod recursion;
 pub mod size;
+pub mod size_mode;
+pub mod sniff;
 pub mod sorting;
 pub mod symlink_arrow;
 pub mod symlinks;
+pub mod threads;
+pub mod time_field;
 pub mod total_size;
+pub mod total_size_cache;
 pub mod truncate_owner;
+pub mod type_filter;
 
 pub use blocks::Blocks;
 pub use color::Color;
 pub use color::{ColorOption, ThemeOption};
 pub use date::DateFlag;
 pub use dereference::Dereference;
+pub use dir_count::DirCount;
 pub use display::Display;
+pub use duplicates::Duplicates;
+pub use extended_attributes::ExtendedAttributes;
+pub use extensions::Extensions;
+pub use format::FormatFlag;
+pub use format_template::FormatTemplate;
+pub use git_dim_ignored::GitDimIgnored;
+pub use git_hide_ignored::GitHideIgnored;
+pub use git_rev::GitRev;
+pub use git_summary::GitSummary;
+pub use gitignore::GitIgnore;
+pub use hash::HashFlag;
 pub use header::Header;
 pub use hyperlink::HyperlinkOption;
 pub use icons::IconOption;
 pub use icons::IconTheme;
 pub use icons::Icons;
 pub use ignore_globs::IgnoreGlobs;
+pub use include_globs::IncludeGlobs;
 pub use indicators::Indicators;
 pub use layout::Layout;
+pub use list_acl::ListAcl;
 pub use literal::Literal;
+pub use mountpoint_indicator::MountpointIndicator;
+pub use one_file_system::OneFileSystem;
 pub use permission::PermissionFlag;
+pub use predicates::Predicates;
 pub use recursion::Recursion;
 pub use size::SizeFlag;
+pub use size_mode::SizeMode;
+pub use sniff::Sniff;
 pub use sorting::DirGrouping;
 pub use sorting::SortColumn;
 pub use sorting::SortOrder;
 pub use sorting::Sorting;
 pub use symlink_arrow::SymlinkArrow;
 pub use symlinks::NoSymlink;
+pub use threads::Threads;
+pub use time_field::TimeField;
 pub use total_size::TotalSize;
+pub use total_size_cache::TotalSizeCache;
 pub use truncate_owner::TruncateOwner;
+pub use type_filter::TypeFilter;
 
 use crate::app::Cli;
 use crate::config_file::Config;
 
 use clap::Error;
+use clap::error::ErrorKind;
 
 #[cfg(doc)]
 use yaml_rust::Yaml;
@@ -58,20 +105,43 @@ use yaml_rust::Yaml;
 #[derive(Clone, Debug, Default)]
 pub struct Flags {
     pub blocks: Blocks,
+    pub format_template: FormatTemplate,
     pub color: Color,
     pub date: DateFlag,
+    pub time: TimeField,
     pub dereference: Dereference,
     pub display: Display,
     pub display_indicators: Indicators,
+    pub extended_attributes: ExtendedAttributes,
+    pub list_acl: ListAcl,
+    pub mountpoint_indicator: MountpointIndicator,
+    pub format: FormatFlag,
     pub icons: Icons,
     pub ignore_globs: IgnoreGlobs,
+    pub gitignore: GitIgnore,
+    pub git_summary: GitSummary,
+    pub git_hide_ignored: GitHideIgnored,
+    pub git_dim_ignored: GitDimIgnored,
+    pub git_rev: GitRev,
+    pub include_globs: IncludeGlobs,
+    pub types: TypeFilter,
+    pub extensions: Extensions,
+    pub predicates: Predicates,
     pub layout: Layout,
     pub no_symlink: NoSymlink,
     pub recursion: Recursion,
+    pub one_file_system: OneFileSystem,
     pub size: SizeFlag,
+    pub size_mode: SizeMode,
+    pub dir_count: DirCount,
+    pub hash: HashFlag,
+    pub sniff: Sniff,
     pub permission: PermissionFlag,
     pub sorting: Sorting,
     pub total_size: TotalSize,
+    pub total_size_cache: TotalSizeCache,
+    pub duplicates: Duplicates,
+    pub threads: Threads,
     pub symlink_arrow: SymlinkArrow,
     pub hyperlink: HyperlinkOption,
     pub header: Header,
@@ -85,24 +155,61 @@ impl Flags {
     /// # Errors
     ///
     /// This can return an [Error], when either the building of the ignore globs or the parsing of
-    /// the recursion depth parameter fails.
+    /// the recursion depth parameter fails, or when a format template is used with the tree
+    /// layout, which cannot lay out its text.
     pub fn configure_from(cli: &Cli, config: &Config) -> Result<Self, Error> {
+        let time = TimeField::configure_from(cli, config);
+        let format_template = FormatTemplate::configure_from(cli, config)?;
+        let blocks = format_template
+            .blocks()
+            .unwrap_or_else(|| Blocks::configure_from(cli, config));
+        let layout = Layout::configure_from(cli, config);
+        if format_template.is_active() && layout == Layout::Tree {
+            return Err(Error::raw(
+                ErrorKind::ArgumentConflict,
+                "--format-template cannot be used with --tree\n",
+            ));
+        }
+
         Ok(Self {
-            blocks: Blocks::configure_from(cli, config),
+            blocks: blocks.with_time(time),
+            format_template,
             color: Color::configure_from(cli, config),
             date: DateFlag::configure_from(cli, config),
+            time,
             dereference: Dereference::configure_from(cli, config),
             display: Display::configure_from(cli, config),
-            layout: Layout::configure_from(cli, config),
+            format: FormatFlag::configure_from(cli, config),
+            layout,
             size: SizeFlag::configure_from(cli, config),
+            size_mode: SizeMode::configure_from(cli, config),
+            dir_count: DirCount::configure_from(cli, config),
+            hash: HashFlag::configure_from(cli, config)?,
+            sniff: Sniff::configure_from(cli, config),
             permission: PermissionFlag::configure_from(cli, config),
             display_indicators: Indicators::configure_from(cli, config),
+            extended_attributes: ExtendedAttributes::configure_from(cli, config),
+            list_acl: ListAcl::configure_from(cli, config),
+            mountpoint_indicator: MountpointIndicator::configure_from(cli, config),
             icons: Icons::configure_from(cli, config),
             ignore_globs: IgnoreGlobs::configure_from(cli, config)?,
+            gitignore: GitIgnore::configure_from(cli, config),
+            git_summary: GitSummary::configure_from(cli, config),
+            git_hide_ignored: GitHideIgnored::configure_from(cli, config),
+            git_dim_ignored: GitDimIgnored::configure_from(cli, config),
+            git_rev: GitRev::configure_from(cli, config),
+            include_globs: IncludeGlobs::configure_from(cli, config)?,
+            types: TypeFilter::configure_from(cli, config),
+            extensions: Extensions::configure_from(cli, config),
+            predicates: Predicates::configure_from(cli, config)?,
             no_symlink: NoSymlink::configure_from(cli, config),
             recursion: Recursion::configure_from(cli, config),
+            one_file_system: OneFileSystem::configure_from(cli, config),
             sorting: Sorting::configure_from(cli, config),
             total_size: TotalSize::configure_from(cli, config),
+            total_size_cache: TotalSizeCache::configure_from(cli, config),
+            duplicates: Duplicates::configure_from(cli, config),
+            threads: Threads::configure_from(cli, config),
             symlink_arrow: SymlinkArrow::configure_from(cli, config),
             hyperlink: HyperlinkOption::configure_from(cli, config),
             header: Header::configure_from(cli, config),
@@ -1,7 +1,7 @@
 //! This module defines the [Blocks] struct. To set it up from [Cli], a [Config] and its
 //! [Default] value, use its [configure_from](Blocks::configure_from) method.
 
-use super::Configurable;
+use super::{Configurable, FormatFlag, SortColumn, TimeField};
 use crate::app::Cli;
 use crate::config_file::Config;
 use crate::print_error;
@@ -46,6 +46,16 @@ impl Blocks {
         }
     }
 
+    /// Replaces the [Date](Block::Date) blocks by the timestamp selected with `--time`.
+    pub fn with_time(mut self, time: TimeField) -> Self {
+        for block in &mut self.0 {
+            if *block == Block::Date {
+                *block = Block::Time(time);
+            }
+        }
+        self
+    }
+
     pub fn displays_size(&self) -> bool {
         self.0.contains(&Block::Size)
     }
@@ -68,7 +78,7 @@ impl Blocks {
     }
 
     /// Checks whether `self` already contains a [Block] of variant [GitStatus](Block::GitStatus).
-    fn contains_git_status(&self) -> bool {
+    pub fn contains_git_status(&self) -> bool {
         self.0.contains(&Block::GitStatus)
     }
 
@@ -88,6 +98,42 @@ impl Blocks {
             self.add_git_status()
         }
     }
+
+    /// Put a [Block] of variant [Hash](Block::Hash) on the left of [Name](Block::Name), if
+    /// `self` does not already contain a Block of that variant.
+    fn optional_add_hash(&mut self) {
+        if self.0.contains(&Block::Hash) {
+            return;
+        }
+        match self.0.iter().position(|&b| b == Block::Name) {
+            Some(position) => self.0.insert(position, Block::Hash),
+            None => self.0.push(Block::Hash),
+        }
+    }
+
+    /// Whether the content hash of the files is needed, to display or to sort them.
+    pub fn needs_hash(&self, sort_column: SortColumn) -> bool {
+        self.0.contains(&Block::Hash) || sort_column == SortColumn::Hash
+    }
+
+    /// Checks whether `self` contains one of the blocks showing the last commit of the files.
+    pub fn displays_git_commit(&self) -> bool {
+        self.0
+            .iter()
+            .any(|b| matches!(b, Block::GitCommit | Block::GitAuthor | Block::GitDate))
+    }
+
+    /// Checks whether `self` contains a [Block] of variant [GitDiff](Block::GitDiff).
+    pub fn displays_git_diff(&self) -> bool {
+        self.0.contains(&Block::GitDiff)
+    }
+
+    /// Whether a [GitCache] must be read for the blocks of `self`.
+    ///
+    /// [GitCache]: crate::git::GitCache
+    pub fn uses_git(&self) -> bool {
+        self.contains_git_status() || self.displays_git_commit() || self.displays_git_diff()
+    }
 }
 
 impl Configurable<Self> for Blocks {
@@ -100,7 +146,8 @@ impl Configurable<Self> for Blocks {
     ///
     /// No matter if the "long" argument was passed, if the "inode" argument is passed and the
     /// `Blocks` does not contain a [Block] of variant [INode](Block::INode) yet, one is prepended
-    /// to the returned value.
+    /// to the returned value. The "git" argument adds a [GitStatus](Block::GitStatus) block in
+    /// the long view and the machine readable formats.
     fn configure_from(cli: &Cli, config: &Config) -> Self {
         let mut blocks = if cli.long {
             Self::long()
@@ -125,10 +172,16 @@ impl Configurable<Self> for Blocks {
             blocks.optional_prepend_inode();
         }
 
-        if !cfg!(feature = "no-git") && cli.git && cli.long {
+        // the machine readable formats hold the Git status without the long view
+        let machine_readable = FormatFlag::configure_from(cli, config).is_machine_readable();
+        if !cfg!(feature = "no-git") && cli.git && (cli.long || machine_readable) {
             blocks.optional_add_git_status();
         }
 
+        if cli.hash.is_some() && cli.long {
+            blocks.optional_add_hash();
+        }
+
         blocks
     }
 
@@ -191,10 +244,23 @@ pub enum Block {
     Size,
     SizeValue,
     Date,
+    /// A date block showing a given timestamp, several of them can be displayed at once.
+    Time(TimeField),
     Name,
     INode,
     Links,
+    Xattrs,
+    Acl,
+    Capabilities,
+    InodeFlags,
+    Hash,
+    Mime,
+    Entries,
     GitStatus,
+    GitCommit,
+    GitAuthor,
+    GitDate,
+    GitDiff,
 }
 
 impl Block {
@@ -202,6 +268,13 @@ impl Block {
         match self {
             Block::INode => "INode",
             Block::Links => "Links",
+            Block::Xattrs => "XAttrs",
+            Block::Acl => "ACL",
+            Block::Capabilities => "Capabilities",
+            Block::InodeFlags => "Flags",
+            Block::Hash => "Hash",
+            Block::Mime => "MIME type",
+            Block::Entries => "Entries",
             Block::Permission => "Permissions",
             Block::User => "User",
             Block::Group => "Group",
@@ -209,8 +282,13 @@ impl Block {
             Block::Size => "Size",
             Block::SizeValue => "SizeValue",
             Block::Date => "Date Modified",
+            Block::Time(time) => time.header(),
             Block::Name => "Name",
             Block::GitStatus => "Git",
+            Block::GitCommit => "Commit",
+            Block::GitAuthor => "Author",
+            Block::GitDate => "Committed",
+            Block::GitDiff => "Diff",
         }
     }
 }
@@ -227,10 +305,25 @@ impl TryFrom<&str> for Block {
             "size" => Ok(Self::Size),
             "size_value" => Ok(Self::SizeValue),
             "date" => Ok(Self::Date),
+            "mtime" => Ok(Self::Time(TimeField::Modified)),
+            "atime" => Ok(Self::Time(TimeField::Accessed)),
+            "ctime" => Ok(Self::Time(TimeField::Changed)),
+            "btime" => Ok(Self::Time(TimeField::Birth)),
             "name" => Ok(Self::Name),
             "inode" => Ok(Self::INode),
             "links" => Ok(Self::Links),
+            "xattrs" => Ok(Self::Xattrs),
+            "acl" => Ok(Self::Acl),
+            "caps" => Ok(Self::Capabilities),
+            "chattr" => Ok(Self::InodeFlags),
+            "hash" => Ok(Self::Hash),
+            "mime" => Ok(Self::Mime),
+            "entries" => Ok(Self::Entries),
             "git" => Ok(Self::GitStatus),
+            "git-commit" => Ok(Self::GitCommit),
+            "git-author" => Ok(Self::GitAuthor),
+            "git-date" => Ok(Self::GitDate),
+            "git-diff" => Ok(Self::GitDiff),
             _ => Err(format!("Not a valid block name: {string}")),
         }
     }
@@ -245,7 +338,7 @@ mod test_blocks {
 
     use crate::app::Cli;
     use crate::config_file::Config;
-    use crate::flags::Configurable;
+    use crate::flags::{Configurable, TimeField};
 
     #[test]
     fn test_configure_from_without_long() {
@@ -424,6 +517,22 @@ mod test_blocks {
         );
     }
 
+    #[cfg(not(feature = "no-git"))]
+    #[test]
+    fn test_git_block_in_machine_readable_format() {
+        let cli = Cli::try_parse_from(["lsd", "--git", "--format", "json"]).unwrap();
+        assert_eq!(
+            Blocks(vec![Block::GitStatus, Block::Name]),
+            Blocks::configure_from(&cli, &Config::with_none())
+        );
+
+        let cli = Cli::try_parse_from(["lsd", "--git"]).unwrap();
+        assert_eq!(
+            Blocks(vec![Block::Name]),
+            Blocks::configure_from(&cli, &Config::with_none())
+        );
+    }
+
     #[test]
     fn test_from_config_none() {
         assert_eq!(None, Blocks::from_config(&Config::with_none()));
@@ -516,11 +625,29 @@ mod test_blocks {
         let parsed_blocks = Blocks::from_cli(&cli).unwrap();
         assert_eq!(test_blocks, parsed_blocks);
     }
+
+    #[test]
+    fn test_with_time() {
+        let blocks = Blocks(vec![
+            Block::Date,
+            Block::Time(TimeField::Modified),
+            Block::Name,
+        ]);
+        assert_eq!(
+            Blocks(vec![
+                Block::Time(TimeField::Changed),
+                Block::Time(TimeField::Modified),
+                Block::Name,
+            ]),
+            blocks.with_time(TimeField::Changed)
+        );
+    }
 }
 
 #[cfg(test)]
 mod test_block {
-    use super::Block;
+    use super::{Block, Blocks};
+    use crate::flags::TimeField;
 
     use std::convert::TryFrom;
 
@@ -562,6 +689,16 @@ mod test_block {
         assert_eq!(Ok(Block::Date), Block::try_from("date"));
     }
 
+    #[test]
+    fn test_time() {
+        assert_eq!(
+            Ok(Block::Time(TimeField::Accessed)),
+            Block::try_from("atime")
+        );
+        assert_eq!(Ok(Block::Time(TimeField::Birth)), Block::try_from("btime"));
+        assert!(Block::try_from("access").is_err());
+    }
+
     #[test]
     fn test_name() {
         assert_eq!(Ok(Block::Name), Block::try_from("name"));
@@ -577,6 +714,41 @@ mod test_block {
         assert_eq!(Ok(Block::Links), Block::try_from("links"));
     }
 
+    #[test]
+    fn test_xattrs() {
+        assert_eq!(Ok(Block::Xattrs), Block::try_from("xattrs"));
+    }
+
+    #[test]
+    fn test_acl() {
+        assert_eq!(Ok(Block::Acl), Block::try_from("acl"));
+    }
+
+    #[test]
+    fn test_caps() {
+        assert_eq!(Ok(Block::Capabilities), Block::try_from("caps"));
+    }
+
+    #[test]
+    fn test_chattr() {
+        assert_eq!(Ok(Block::InodeFlags), Block::try_from("chattr"));
+    }
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(Ok(Block::Hash), Block::try_from("hash"));
+    }
+
+    #[test]
+    fn test_mime() {
+        assert_eq!(Ok(Block::Mime), Block::try_from("mime"));
+    }
+
+    #[test]
+    fn test_entries() {
+        assert_eq!(Ok(Block::Entries), Block::try_from("entries"));
+    }
+
     #[test]
     fn test_context() {
         assert_eq!(Ok(Block::Context), Block::try_from("context"));
@@ -586,6 +758,13 @@ mod test_block {
     fn test_block_headers() {
         assert_eq!(Block::INode.get_header(), "INode");
         assert_eq!(Block::Links.get_header(), "Links");
+        assert_eq!(Block::Xattrs.get_header(), "XAttrs");
+        assert_eq!(Block::Acl.get_header(), "ACL");
+        assert_eq!(Block::Capabilities.get_header(), "Capabilities");
+        assert_eq!(Block::InodeFlags.get_header(), "Flags");
+        assert_eq!(Block::Hash.get_header(), "Hash");
+        assert_eq!(Block::Mime.get_header(), "MIME type");
+        assert_eq!(Block::Entries.get_header(), "Entries");
         assert_eq!(Block::Permission.get_header(), "Permissions");
         assert_eq!(Block::User.get_header(), "User");
         assert_eq!(Block::Group.get_header(), "Group");
@@ -593,12 +772,48 @@ mod test_block {
         assert_eq!(Block::Size.get_header(), "Size");
         assert_eq!(Block::SizeValue.get_header(), "SizeValue");
         assert_eq!(Block::Date.get_header(), "Date Modified");
+        assert_eq!(
+            Block::Time(TimeField::Accessed).get_header(),
+            "Date Accessed"
+        );
         assert_eq!(Block::Name.get_header(), "Name");
         assert_eq!(Block::GitStatus.get_header(), "Git");
+        assert_eq!(Block::GitCommit.get_header(), "Commit");
+        assert_eq!(Block::GitAuthor.get_header(), "Author");
+        assert_eq!(Block::GitDate.get_header(), "Committed");
+        assert_eq!(Block::GitDiff.get_header(), "Diff");
     }
 
     #[test]
     fn test_git_status() {
         assert_eq!(Ok(Block::GitStatus), Block::try_from("git"));
     }
+
+    #[test]
+    fn test_git_commit() {
+        assert_eq!(Ok(Block::GitCommit), Block::try_from("git-commit"));
+        assert_eq!(Ok(Block::GitAuthor), Block::try_from("git-author"));
+        assert_eq!(Ok(Block::GitDate), Block::try_from("git-date"));
+    }
+
+    #[test]
+    fn test_displays_git_commit() {
+        let blocks = Blocks(vec![Block::Name, Block::GitStatus]);
+        assert!(!blocks.displays_git_commit());
+        assert!(blocks.uses_git());
+
+        let blocks = Blocks(vec![Block::GitDate, Block::Name]);
+        assert!(blocks.displays_git_commit());
+        assert!(blocks.uses_git());
+    }
+
+    #[test]
+    fn test_git_diff() {
+        assert_eq!(Ok(Block::GitDiff), Block::try_from("git-diff"));
+
+        let blocks = Blocks(vec![Block::Name, Block::GitDiff]);
+        assert!(blocks.displays_git_diff());
+        assert!(!blocks.displays_git_commit());
+        assert!(blocks.uses_git());
+    }
 }
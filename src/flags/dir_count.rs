@@ -0,0 +1,79 @@
+//! This module defines the [DirCount] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+/// The flag showing whether the size block shows the number of entries of directories instead
+/// of their size.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct DirCount(pub bool);
+
+impl Configurable<Self> for DirCount {
+    /// Get a potential `DirCount` value from [Cli].
+    ///
+    /// If the "dir-count" argument is passed, this returns a `DirCount` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.dir_count {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `DirCount` value from a [Config].
+    ///
+    /// If the `Config::dir_count` has value,
+    /// this returns it as the value of the `DirCount`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.dir_count.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::DirCount;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, DirCount::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_true() {
+        let argv = ["lsd", "--dir-count"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(DirCount(true)), DirCount::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, DirCount::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.dir_count = Some(true);
+        assert_eq!(Some(DirCount(true)), DirCount::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.dir_count = Some(false);
+        assert_eq!(Some(DirCount(false)), DirCount::from_config(&c));
+    }
+}
@@ -0,0 +1,79 @@
+//! This module defines the [Duplicates] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+/// The flag showing whether to list the groups of files with the same content instead of the
+/// entries.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Duplicates(pub bool);
+
+impl Configurable<Self> for Duplicates {
+    /// Get a potential `Duplicates` value from [Cli].
+    ///
+    /// If the "duplicates" argument is passed, this returns a `Duplicates` with value `true` in
+    /// a [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.duplicates {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `Duplicates` value from a [Config].
+    ///
+    /// If the `Config::duplicates` has value,
+    /// this returns it as the value of the `Duplicates`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.duplicates.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::Duplicates;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, Duplicates::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_true() {
+        let argv = ["lsd", "--duplicates"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(Duplicates(true)), Duplicates::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Duplicates::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.duplicates = Some(true);
+        assert_eq!(Some(Duplicates(true)), Duplicates::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.duplicates = Some(false);
+        assert_eq!(Some(Duplicates(false)), Duplicates::from_config(&c));
+    }
+}
@@ -0,0 +1,87 @@
+//! This module defines the [ExtendedAttributes] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+/// The flag showing whether to list the extended attributes under each entry in long mode.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct ExtendedAttributes(pub bool);
+
+impl Configurable<Self> for ExtendedAttributes {
+    /// Get a potential `ExtendedAttributes` value from [Cli].
+    ///
+    /// If the "extended-attributes" argument is passed, this returns an `ExtendedAttributes` with
+    /// value `true` in a [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.extended_attributes {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `ExtendedAttributes` value from a [Config].
+    ///
+    /// If the `Config::extended-attributes` has value,
+    /// this returns it as the value of the `ExtendedAttributes`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.extended_attributes.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::ExtendedAttributes;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, ExtendedAttributes::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_true() {
+        let argv = ["lsd", "-@"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            Some(ExtendedAttributes(true)),
+            ExtendedAttributes::from_cli(&cli)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, ExtendedAttributes::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.extended_attributes = Some(true);
+        assert_eq!(
+            Some(ExtendedAttributes(true)),
+            ExtendedAttributes::from_config(&c)
+        );
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.extended_attributes = Some(false);
+        assert_eq!(
+            Some(ExtendedAttributes(false)),
+            ExtendedAttributes::from_config(&c)
+        );
+    }
+}
@@ -0,0 +1,108 @@
+//! This module defines the [Extensions] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+/// The flag showing which file extensions to list, all of them when empty.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct Extensions(pub Vec<String>);
+
+impl Extensions {
+    /// Whether an entry with this extension should be listed, ignoring the case.
+    pub fn matches(&self, extension: Option<&str>) -> bool {
+        if self.0.is_empty() {
+            return true;
+        }
+
+        extension.is_some_and(|extension| {
+            self.0
+                .iter()
+                .any(|wanted| wanted.eq_ignore_ascii_case(extension))
+        })
+    }
+}
+
+impl Configurable<Self> for Extensions {
+    /// Get a potential `Extensions` value from [Cli].
+    ///
+    /// If the "ext" argument is passed, this returns the given extensions, without their
+    /// leading dot, in a [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.ext.is_empty() {
+            None
+        } else {
+            Some(Self(
+                cli.ext
+                    .iter()
+                    .map(|ext| ext.trim_start_matches('.').to_string())
+                    .collect(),
+            ))
+        }
+    }
+
+    /// Get a potential `Extensions` value from a [Config].
+    ///
+    /// If the `Config::extensions` has value, this returns it as the value of the `Extensions`,
+    /// in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.extensions.as_ref().map(|extensions| {
+            Self(
+                extensions
+                    .iter()
+                    .map(|ext| ext.trim_start_matches('.').to_string())
+                    .collect(),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::Extensions;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, Extensions::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_list() {
+        let argv = ["lsd", "--ext", "rs,.toml"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            Some(Extensions(vec!["rs".into(), "toml".into()])),
+            Extensions::from_cli(&cli)
+        );
+    }
+
+    #[test]
+    fn test_from_config() {
+        let mut c = Config::with_none();
+        c.extensions = Some(vec!["md".into()]);
+        assert_eq!(
+            Some(Extensions(vec!["md".into()])),
+            Extensions::from_config(&c)
+        );
+    }
+
+    #[test]
+    fn test_matches() {
+        let extensions = Extensions(vec!["rs".into()]);
+        assert!(extensions.matches(Some("rs")));
+        assert!(extensions.matches(Some("RS")));
+        assert!(!extensions.matches(Some("toml")));
+        assert!(!extensions.matches(None));
+        assert!(Extensions::default().matches(None));
+    }
+}
@@ -0,0 +1,130 @@
+//! This module defines the [FormatFlag]. To set it up from [Cli], a [Config] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+use serde::Deserialize;
+
+/// The flag showing which output format to print.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum FormatFlag {
+    /// The variant to print the listing using the grid or tree layout.
+    #[default]
+    Default,
+    /// The variant to print the listing as a single JSON array.
+    Json,
+    /// The variant to print one JSON object per line.
+    Ndjson,
+    /// The variant to print the blocks as comma separated values.
+    Csv,
+    /// The variant to print the blocks as tab separated values.
+    Tsv,
+}
+
+impl FormatFlag {
+    fn from_arg_str(value: &str) -> Self {
+        match value {
+            "default" => Self::Default,
+            "json" => Self::Json,
+            "ndjson" => Self::Ndjson,
+            "csv" => Self::Csv,
+            "tsv" => Self::Tsv,
+            // Invalid value should be handled by `clap` when building an `Cli`
+            other => unreachable!("Invalid value '{other}' for 'format'"),
+        }
+    }
+
+    /// Whether the output is meant to be consumed by other programs rather than read.
+    pub fn is_machine_readable(&self) -> bool {
+        *self != Self::Default
+    }
+}
+
+impl Configurable<Self> for FormatFlag {
+    /// Get a potential `FormatFlag` variant from [Cli].
+    ///
+    /// If the "format" argument is passed, the corresponding `FormatFlag` variant is returned
+    /// in a [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        cli.format.as_deref().map(Self::from_arg_str)
+    }
+
+    /// Get a potential `FormatFlag` variant from a [Config].
+    ///
+    /// If the `Config::format` has value, this returns it in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.format
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::FormatFlag;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_default() {
+        assert_eq!(FormatFlag::Default, FormatFlag::default());
+    }
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, FormatFlag::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_json() {
+        let argv = ["lsd", "--format", "json"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(FormatFlag::Json), FormatFlag::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_ndjson() {
+        let argv = ["lsd", "--format", "ndjson"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(FormatFlag::Ndjson), FormatFlag::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_csv_tsv() {
+        let argv = ["lsd", "--format", "csv"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(FormatFlag::Csv), FormatFlag::from_cli(&cli));
+
+        let argv = ["lsd", "--format", "tsv"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(FormatFlag::Tsv), FormatFlag::from_cli(&cli));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_cli_unknown() {
+        let argv = ["lsd", "--format", "xml"];
+        let _ = Cli::try_parse_from(argv).unwrap();
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, FormatFlag::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_json() {
+        let mut c = Config::with_none();
+        c.format = Some(FormatFlag::Json);
+        assert_eq!(Some(FormatFlag::Json), FormatFlag::from_config(&c));
+    }
+}
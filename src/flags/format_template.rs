@@ -0,0 +1,336 @@
+//! This module defines the [FormatTemplate] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](FormatTemplate::configure_from) method.
+
+use super::blocks::Block;
+use super::{Blocks, DateFlag, SizeFlag};
+use crate::app::{self, Cli};
+use crate::config_file::Config;
+
+use clap::Error;
+use clap::error::ErrorKind;
+
+/// The template of the lines of the long view, like `{perm} {user:>8} {size:b} {name}`.
+///
+/// It is empty unless a template is set, in which case it replaces the [Blocks].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FormatTemplate(pub Vec<Segment>);
+
+/// A part of a [FormatTemplate].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Segment {
+    /// Text copied as is, with `{{` and `}}` unescaped.
+    Literal(String),
+    Field(Field),
+}
+
+/// A `{block:[align][width][format]}` placeholder.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Field {
+    pub block: Block,
+    pub align: Align,
+    /// The minimum width of the column, which is otherwise as wide as its widest value.
+    pub width: Option<usize>,
+    pub size: Option<SizeFlag>,
+    pub date: Option<DateFlag>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Align {
+    #[default]
+    Left,
+    Right,
+    Center,
+}
+
+impl FormatTemplate {
+    /// Returns a value from either [Cli], a [Config] or a [Default] value. The template of the
+    /// [Config] is only used with the "long" argument, like its blocks.
+    ///
+    /// # Errors
+    ///
+    /// If a template does not parse, or uses an unknown field or format.
+    pub fn configure_from(cli: &Cli, config: &Config) -> Result<Self, Error> {
+        let config_template = config
+            .format_template
+            .as_deref()
+            .map(Self::parse)
+            .transpose()?;
+
+        match cli.format_template.as_deref() {
+            Some(template) => Self::parse(template),
+            None if cli.long => Ok(config_template.unwrap_or_default()),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.0.is_empty()
+    }
+
+    pub fn fields(&self) -> impl Iterator<Item = &Field> {
+        self.0.iter().filter_map(|segment| match segment {
+            Segment::Field(field) => Some(field),
+            Segment::Literal(_) => None,
+        })
+    }
+
+    /// The blocks of the fields, in their order, when a template is set.
+    pub fn blocks(&self) -> Option<Blocks> {
+        if self.is_active() {
+            Some(Blocks(self.fields().map(|field| field.block).collect()))
+        } else {
+            None
+        }
+    }
+
+    fn parse(template: &str) -> Result<Self, Error> {
+        let error = |reason: String| {
+            Error::raw(
+                ErrorKind::ValueValidation,
+                format!("invalid format template '{template}': {reason}\n"),
+            )
+        };
+
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut placeholder = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => placeholder.push(c),
+                            None => return Err(error("unclosed '{'".to_string())),
+                        }
+                    }
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(Segment::Field(Field::parse(&placeholder).map_err(error)?));
+                }
+                '}' => {
+                    return Err(error(
+                        "unmatched '}', use '}}' for a literal one".to_string(),
+                    ));
+                }
+                _ => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        if segments
+            .iter()
+            .all(|segment| matches!(segment, Segment::Literal(_)))
+        {
+            return Err(error("no field to display".to_string()));
+        }
+        Ok(Self(segments))
+    }
+}
+
+impl Field {
+    /// Parse the inside of a placeholder, e.g. `size:>8b` or `mtime:%F`.
+    fn parse(placeholder: &str) -> Result<Self, String> {
+        let (name, spec) = placeholder.split_once(':').unwrap_or((placeholder, ""));
+        let block = match name.trim() {
+            "perm" => Block::Permission,
+            // only used internally to align the values of the size block
+            "size_value" => return Err("unknown field 'size_value'".to_string()),
+            name => Block::try_from(name).map_err(|_| format!("unknown field '{name}'"))?,
+        };
+
+        let mut spec = spec.chars().peekable();
+        let align = match spec.peek() {
+            Some('<') => Align::Left,
+            Some('>') => Align::Right,
+            Some('^') => Align::Center,
+            _ => Align::default(),
+        };
+        if matches!(spec.peek(), Some('<' | '>' | '^')) {
+            spec.next();
+        }
+        let mut width = String::new();
+        while let Some(digit) = spec.next_if(char::is_ascii_digit) {
+            width.push(digit);
+        }
+        let width = match width.is_empty() {
+            true => None,
+            false => Some(
+                width
+                    .parse()
+                    .map_err(|_| format!("invalid width '{width}'"))?,
+            ),
+        };
+
+        let format: String = spec.collect();
+        let mut field = Self {
+            block,
+            align,
+            width,
+            size: None,
+            date: None,
+        };
+        if format.is_empty() {
+            return Ok(field);
+        }
+        match block {
+            Block::Size => {
+                field.size = Some(match format.as_str() {
+                    "b" | "bytes" => SizeFlag::Bytes,
+                    "s" | "short" => SizeFlag::Short,
+                    "d" | "default" => SizeFlag::Default,
+                    _ => return Err(format!("invalid size format '{format}', use b, s or d")),
+                })
+            }
+            Block::Date | Block::Time(_) => {
+                app::validate_time_format(&format)?;
+                field.date = Some(DateFlag::Formatted(format));
+            }
+            _ => return Err(format!("the field '{name}' takes no format")),
+        }
+        Ok(field)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+    use clap::error::ErrorKind;
+
+    use super::{Align, Field, FormatTemplate, Segment};
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::blocks::Block;
+    use crate::flags::{DateFlag, SizeFlag, TimeField};
+
+    fn field(block: Block) -> Field {
+        Field {
+            block,
+            align: Align::Left,
+            width: None,
+            size: None,
+            date: None,
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let template =
+            FormatTemplate::parse("{perm} {user:>8} {size:b} {mtime:%F} {name}").unwrap();
+        let space = || Segment::Literal(" ".to_string());
+        assert_eq!(
+            vec![
+                Segment::Field(field(Block::Permission)),
+                space(),
+                Segment::Field(Field {
+                    align: Align::Right,
+                    width: Some(8),
+                    ..field(Block::User)
+                }),
+                space(),
+                Segment::Field(Field {
+                    size: Some(SizeFlag::Bytes),
+                    ..field(Block::Size)
+                }),
+                space(),
+                Segment::Field(Field {
+                    date: Some(DateFlag::Formatted("%F".to_string())),
+                    ..field(Block::Time(TimeField::Modified))
+                }),
+                space(),
+                Segment::Field(field(Block::Name)),
+            ],
+            template.0
+        );
+    }
+
+    #[test]
+    fn test_parse_escapes_and_alignment() {
+        let template = FormatTemplate::parse("{{{size:^}}} {name:<20}").unwrap();
+        assert_eq!(
+            vec![
+                Segment::Literal("{".to_string()),
+                Segment::Field(Field {
+                    align: Align::Center,
+                    ..field(Block::Size)
+                }),
+                Segment::Literal("} ".to_string()),
+                Segment::Field(Field {
+                    width: Some(20),
+                    ..field(Block::Name)
+                }),
+            ],
+            template.0
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        for template in [
+            "{name",
+            "name}",
+            "{nmae}",
+            "{size:kb}",
+            "{user:%F}",
+            "{mtime:%Q}",
+            "{size_value}",
+            "no fields",
+            "",
+        ] {
+            let err = FormatTemplate::parse(template).unwrap_err();
+            assert_eq!(ErrorKind::ValueValidation, err.kind(), "{template}");
+        }
+    }
+
+    #[test]
+    fn test_blocks() {
+        let template = FormatTemplate::parse("{git} {date} {name}").unwrap();
+        assert_eq!(
+            vec![Block::GitStatus, Block::Date, Block::Name],
+            template.blocks().unwrap().0
+        );
+        assert_eq!(None, FormatTemplate::default().blocks());
+    }
+
+    #[test]
+    fn test_configure_from() {
+        let mut c = Config::with_none();
+        c.format_template = Some("{size} {name}".to_string());
+
+        let cli = Cli::try_parse_from(["lsd"]).unwrap();
+        assert!(
+            !FormatTemplate::configure_from(&cli, &c)
+                .unwrap()
+                .is_active()
+        );
+
+        let cli = Cli::try_parse_from(["lsd", "-l"]).unwrap();
+        let template = FormatTemplate::configure_from(&cli, &c).unwrap();
+        assert_eq!(3, template.0.len());
+
+        let cli = Cli::try_parse_from(["lsd", "--format-template", "{name}"]).unwrap();
+        let template = FormatTemplate::configure_from(&cli, &c).unwrap();
+        assert_eq!(vec![Segment::Field(field(Block::Name))], template.0);
+    }
+
+    #[test]
+    fn test_configure_from_invalid_config() {
+        let mut c = Config::with_none();
+        c.format_template = Some("{size".to_string());
+        let cli = Cli::try_parse_from(["lsd"]).unwrap();
+        assert!(FormatTemplate::configure_from(&cli, &c).is_err());
+    }
+}
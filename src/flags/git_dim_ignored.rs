@@ -0,0 +1,79 @@
+//! This module defines the [GitDimIgnored] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+/// The flag showing whether the entries ignored by Git are dimmed, whatever the displayed
+/// blocks.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct GitDimIgnored(pub bool);
+
+impl Configurable<Self> for GitDimIgnored {
+    /// Get a potential `GitDimIgnored` value from [Cli].
+    ///
+    /// If the "git-dim-ignored" argument is passed, this returns a `GitDimIgnored` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.git_dim_ignored {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `GitDimIgnored` value from a [Config].
+    ///
+    /// If the `Config::git_dim_ignored` has value,
+    /// this returns it as the value of the `GitDimIgnored`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.git_dim_ignored.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::GitDimIgnored;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, GitDimIgnored::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_true() {
+        let argv = ["lsd", "--git-dim-ignored"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(GitDimIgnored(true)), GitDimIgnored::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, GitDimIgnored::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.git_dim_ignored = Some(true);
+        assert_eq!(Some(GitDimIgnored(true)), GitDimIgnored::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.git_dim_ignored = Some(false);
+        assert_eq!(Some(GitDimIgnored(false)), GitDimIgnored::from_config(&c));
+    }
+}
@@ -0,0 +1,79 @@
+//! This module defines the [GitHideIgnored] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+/// The flag showing whether the entries ignored by Git are hidden, whatever the displayed
+/// blocks.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct GitHideIgnored(pub bool);
+
+impl Configurable<Self> for GitHideIgnored {
+    /// Get a potential `GitHideIgnored` value from [Cli].
+    ///
+    /// If the "git-hide-ignored" argument is passed, this returns a `GitHideIgnored` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.git_hide_ignored {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `GitHideIgnored` value from a [Config].
+    ///
+    /// If the `Config::git_hide_ignored` has value,
+    /// this returns it as the value of the `GitHideIgnored`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.git_hide_ignored.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::GitHideIgnored;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, GitHideIgnored::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_true() {
+        let argv = ["lsd", "--git-hide-ignored"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(GitHideIgnored(true)), GitHideIgnored::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, GitHideIgnored::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.git_hide_ignored = Some(true);
+        assert_eq!(Some(GitHideIgnored(true)), GitHideIgnored::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.git_hide_ignored = Some(false);
+        assert_eq!(Some(GitHideIgnored(false)), GitHideIgnored::from_config(&c));
+    }
+}
@@ -0,0 +1,71 @@
+//! This module defines the [GitRev] flag. To set it up from [Cli] and its [Default] value, use
+//! the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+/// The flag holding the Git revision whose trees are listed instead of the files on the disk,
+/// like `HEAD~2` or `v1.0`, or `:` for the index. It is [None] to list the files on the disk.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct GitRev(pub Option<String>);
+
+impl Configurable<Self> for GitRev {
+    /// Get a potential `GitRev` value from [Cli].
+    ///
+    /// If the "git-rev" argument is passed, this returns a `GitRev` with its value in a [Some].
+    /// Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        cli.git_rev.clone().map(|rev| Self(Some(rev)))
+    }
+
+    /// The revision is only given on the command line, listing another revision than the files
+    /// on the disk by default would be surprising. This always returns [None].
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::GitRev;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, GitRev::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_rev() {
+        let argv = ["lsd", "--git-rev", "HEAD~2"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            Some(GitRev(Some("HEAD~2".to_string()))),
+            GitRev::from_cli(&cli)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, GitRev::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_configure_from_default() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            GitRev(None),
+            GitRev::configure_from(&cli, &Config::with_none())
+        );
+    }
+}
@@ -0,0 +1,79 @@
+//! This module defines the [GitSummary] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+/// The flag showing whether a summary of the Git repository, with its branch, upstream, stashes
+/// and dirty state, is printed before the listing of the directories inside one.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct GitSummary(pub bool);
+
+impl Configurable<Self> for GitSummary {
+    /// Get a potential `GitSummary` value from [Cli].
+    ///
+    /// If the "git-summary" argument is passed, this returns a `GitSummary` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.git_summary {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `GitSummary` value from a [Config].
+    ///
+    /// If the `Config::git_summary` has value,
+    /// this returns it as the value of the `GitSummary`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.git_summary.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::GitSummary;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, GitSummary::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_true() {
+        let argv = ["lsd", "--git-summary"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(GitSummary(true)), GitSummary::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, GitSummary::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.git_summary = Some(true);
+        assert_eq!(Some(GitSummary(true)), GitSummary::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.git_summary = Some(false);
+        assert_eq!(Some(GitSummary(false)), GitSummary::from_config(&c));
+    }
+}
@@ -0,0 +1,78 @@
+//! This module defines the [GitIgnore] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+/// The flag showing whether to hide the files ignored by `.gitignore` and `.ignore` files.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct GitIgnore(pub bool);
+
+impl Configurable<Self> for GitIgnore {
+    /// Get a potential `GitIgnore` value from [Cli].
+    ///
+    /// If the "gitignore" argument is passed, this returns a `GitIgnore` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.gitignore {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `GitIgnore` value from a [Config].
+    ///
+    /// If the `Config::gitignore` has value,
+    /// this returns it as the value of the `GitIgnore`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.gitignore.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::GitIgnore;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, GitIgnore::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_true() {
+        let argv = ["lsd", "--gitignore"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(GitIgnore(true)), GitIgnore::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, GitIgnore::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.gitignore = Some(true);
+        assert_eq!(Some(GitIgnore(true)), GitIgnore::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.gitignore = Some(false);
+        assert_eq!(Some(GitIgnore(false)), GitIgnore::from_config(&c));
+    }
+}
@@ -0,0 +1,136 @@
+//! This module defines the [HashFlag]. To set it up from [Cli], a [Config] and its [Default]
+//! value, use the [configure_from](HashFlag::configure_from) method.
+
+use crate::app::Cli;
+use crate::config_file::Config;
+use crate::meta::Size;
+
+use clap::Error;
+use clap::error::ErrorKind;
+use serde::Deserialize;
+
+/// The files larger than this are not hashed by default.
+const DEFAULT_MAX_SIZE: u64 = 64 * 1024 * 1024;
+
+/// The algorithm computing the content hash of the files.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    Blake3,
+    Crc32,
+}
+
+impl HashAlgorithm {
+    fn from_arg_str(value: &str) -> Self {
+        match value {
+            "sha256" => Self::Sha256,
+            "blake3" => Self::Blake3,
+            "crc32" => Self::Crc32,
+            // Invalid value should be handled by `clap` when building an `Cli`
+            other => unreachable!("Invalid value '{other}' for 'hash'"),
+        }
+    }
+}
+
+/// The flag setting how the hash block hashes the content of regular files.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub struct HashFlag {
+    pub algorithm: HashAlgorithm,
+    /// The size in bytes above which files are not hashed, so that listing large files does
+    /// not read them all.
+    pub max_size: u64,
+}
+
+impl Default for HashFlag {
+    fn default() -> Self {
+        Self {
+            algorithm: HashAlgorithm::default(),
+            max_size: DEFAULT_MAX_SIZE,
+        }
+    }
+}
+
+impl HashFlag {
+    /// Returns a value from either [Cli], a [Config] or a [Default] value. The algorithm and
+    /// the maximum size are each taken from [Cli] when passed there, and from the [Config]
+    /// otherwise.
+    ///
+    /// # Errors
+    ///
+    /// If the maximum size cannot be parsed.
+    pub fn configure_from(cli: &Cli, config: &Config) -> Result<Self, Error> {
+        let algorithm = cli
+            .hash
+            .as_deref()
+            .map(HashAlgorithm::from_arg_str)
+            .or_else(|| config.hash.as_ref().and_then(|c| c.algorithm))
+            .unwrap_or_default();
+
+        let max_size = match cli
+            .hash_max_size
+            .clone()
+            .or_else(|| config.hash.as_ref().and_then(|c| c.max_size.clone()))
+        {
+            Some(value) => Size::parse_bytes(&value).ok_or_else(|| {
+                Error::raw(
+                    ErrorKind::ValueValidation,
+                    format!("invalid hash-max-size '{value}', expected a size like 10M or 1G\n"),
+                )
+            })?,
+            None => DEFAULT_MAX_SIZE,
+        };
+
+        Ok(Self {
+            algorithm,
+            max_size,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::{HashAlgorithm, HashFlag};
+
+    use crate::app::Cli;
+    use crate::config_file::{self, Config};
+
+    #[test]
+    fn test_configure_from_default() {
+        let cli = Cli::try_parse_from(["lsd"]).unwrap();
+        let flag = HashFlag::configure_from(&cli, &Config::with_none()).unwrap();
+        assert_eq!(HashFlag::default(), flag);
+        assert_eq!(HashAlgorithm::Sha256, flag.algorithm);
+    }
+
+    #[test]
+    fn test_configure_from_cli_over_config() {
+        let argv = ["lsd", "--hash", "blake3"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let mut c = Config::with_none();
+        c.hash = Some(config_file::Hash {
+            algorithm: Some(HashAlgorithm::Crc32),
+            max_size: Some("1M".to_string()),
+        });
+        let flag = HashFlag::configure_from(&cli, &c).unwrap();
+        assert_eq!(HashAlgorithm::Blake3, flag.algorithm);
+        assert_eq!(1024 * 1024, flag.max_size);
+    }
+
+    #[test]
+    fn test_configure_from_invalid_max_size() {
+        let argv = ["lsd", "--hash-max-size", "huge"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert!(HashFlag::configure_from(&cli, &Config::with_none()).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_cli_unknown() {
+        let argv = ["lsd", "--hash", "md5"];
+        let _ = Cli::try_parse_from(argv).unwrap();
+    }
+}
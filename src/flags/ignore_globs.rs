@@ -84,14 +84,14 @@ impl IgnoreGlobs {
     /// Create a [Glob] from a provided pattern.
     ///
     /// This method is mainly a helper to wrap the handling of potential errors.
-    fn create_glob(pattern: &str) -> Result<Glob, Error> {
+    pub(super) fn create_glob(pattern: &str) -> Result<Glob, Error> {
         Glob::new(pattern).map_err(|err| Error::raw(ErrorKind::ValueValidation, err))
     }
 
     /// Create a [GlobSet] from a provided [GlobSetBuilder].
     ///
     /// This method is mainly a helper to wrap the handling of potential errors.
-    fn create_glob_set(builder: &GlobSetBuilder) -> Result<GlobSet, Error> {
+    pub(super) fn create_glob_set(builder: &GlobSetBuilder) -> Result<GlobSet, Error> {
         builder
             .build()
             .map_err(|err| Error::raw(ErrorKind::ValueValidation, err))
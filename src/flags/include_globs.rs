@@ -0,0 +1,131 @@
+//! This module defines the [IncludeGlobs]. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](IncludeGlobs::configure_from) method.
+
+use super::IgnoreGlobs;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+use clap::Error;
+use globset::{GlobSet, GlobSetBuilder};
+
+/// The struct holding a [GlobSet] the names of the listed entries have to match.
+#[derive(Clone, Debug)]
+pub struct IncludeGlobs(pub GlobSet);
+
+impl IncludeGlobs {
+    /// Returns a value from either [Cli], a [Config] or a [Default] value. The first value
+    /// that is not [None] is used. The order of precedence for the value used is:
+    /// - [from_cli](IncludeGlobs::from_cli)
+    /// - [from_config](IncludeGlobs::from_config)
+    /// - [Default::default]
+    ///
+    /// # Errors
+    ///
+    /// If either of the [Glob::new](globset::Glob::new) or [GlobSetBuilder.build] methods
+    /// return an [Err].
+    pub fn configure_from(cli: &Cli, config: &Config) -> Result<Self, Error> {
+        if let Some(value) = Self::from_cli(cli) {
+            return value;
+        }
+
+        if let Some(value) = Self::from_config(config) {
+            return value;
+        }
+
+        Ok(Default::default())
+    }
+
+    /// Get a potential [IncludeGlobs] from [Cli].
+    ///
+    /// If the "include-glob" argument has been passed, this returns a [Result] in a [Some] with
+    /// either the built [IncludeGlobs] or an [Error]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Result<Self, Error>> {
+        if cli.include_glob.is_empty() {
+            return None;
+        }
+
+        Some(Self::from_patterns(&cli.include_glob))
+    }
+
+    /// Get a potential [IncludeGlobs] from a [Config].
+    ///
+    /// If the `Config::include-globs` contains an Array of Strings, this returns a [Result] in a
+    /// [Some] with either the built [IncludeGlobs] or an [Error]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Result<Self, Error>> {
+        let globs = config.include_globs.as_ref()?;
+
+        Some(Self::from_patterns(globs))
+    }
+
+    fn from_patterns(patterns: &[String]) -> Result<Self, Error> {
+        let mut glob_set_builder = GlobSetBuilder::new();
+
+        for pattern in patterns {
+            glob_set_builder.add(IgnoreGlobs::create_glob(pattern)?);
+        }
+
+        IgnoreGlobs::create_glob_set(&glob_set_builder).map(Self)
+    }
+
+    /// Whether a name matches the globs, always `true` when no glob is given.
+    pub fn matches(&self, name: &str) -> bool {
+        self.0.is_empty() || self.0.is_match(name)
+    }
+}
+
+/// The default value of `IncludeGlobs` is the empty [GlobSet], matching every name.
+impl Default for IncludeGlobs {
+    fn default() -> Self {
+        Self(GlobSet::empty())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::IncludeGlobs;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+
+    #[test]
+    fn test_default_matches_everything() {
+        let globs = IncludeGlobs::default();
+        assert!(globs.matches("main.rs"));
+    }
+
+    #[test]
+    fn test_from_cli() {
+        let argv = ["lsd", "--include-glob", "*.rs", "--include-glob", "Cargo.*"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let globs = IncludeGlobs::configure_from(&cli, &Config::with_none()).unwrap();
+        assert!(globs.matches("main.rs"));
+        assert!(globs.matches("Cargo.toml"));
+        assert!(!globs.matches("README.md"));
+    }
+
+    #[test]
+    fn test_from_cli_invalid() {
+        let argv = ["lsd", "--include-glob", "a[b"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert!(IncludeGlobs::configure_from(&cli, &Config::with_none()).is_err());
+    }
+
+    #[test]
+    fn test_from_config() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let mut c = Config::with_none();
+        c.include_globs = Some(vec!["*.md".into()]);
+        let globs = IncludeGlobs::configure_from(&cli, &c).unwrap();
+        assert!(globs.matches("README.md"));
+        assert!(!globs.matches("main.rs"));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert!(IncludeGlobs::from_config(&Config::with_none()).is_none());
+    }
+}
@@ -23,12 +23,18 @@ impl Configurable<Layout> for Layout {
     ///
     /// If any of the "tree", "long" or "oneline" arguments is passed, this returns the
     /// corresponding `Layout` variant in a [Some]. Otherwise if the number of passed "blocks"
-    /// arguments is greater than 1, this also returns the [OneLine](Layout::OneLine) variant.
+    /// arguments is greater than 1, or if a "format-template" is passed, this also returns the
+    /// [OneLine](Layout::OneLine) variant.
     /// Finally if neither of them is passed, this returns [None].
     fn from_cli(cli: &Cli) -> Option<Self> {
         if cli.tree {
             Some(Self::Tree)
-        } else if cli.long || cli.oneline || cli.inode || cli.context || cli.blocks.len() > 1
+        } else if cli.long
+            || cli.oneline
+            || cli.inode
+            || cli.context
+            || cli.blocks.len() > 1
+            || cli.format_template.is_some()
         // TODO: handle this differently
         {
             Some(Self::OneLine)
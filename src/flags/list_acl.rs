@@ -0,0 +1,74 @@
+//! This module defines the [ListAcl] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+/// The flag showing whether to list the ACL entries under each entry in long mode.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct ListAcl(pub bool);
+
+impl Configurable<Self> for ListAcl {
+    /// Get a potential `ListAcl` value from [Cli].
+    ///
+    /// If the "acl" argument is passed, this returns a `ListAcl` with value `true` in a [Some].
+    /// Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.acl { Some(Self(true)) } else { None }
+    }
+
+    /// Get a potential `ListAcl` value from a [Config].
+    ///
+    /// If the `Config::acl` has value,
+    /// this returns it as the value of the `ListAcl`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.acl.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::ListAcl;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, ListAcl::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_true() {
+        let argv = ["lsd", "--acl"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(ListAcl(true)), ListAcl::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, ListAcl::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.acl = Some(true);
+        assert_eq!(Some(ListAcl(true)), ListAcl::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.acl = Some(false);
+        assert_eq!(Some(ListAcl(false)), ListAcl::from_config(&c));
+    }
+}
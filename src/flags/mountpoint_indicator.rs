@@ -0,0 +1,87 @@
+//! This module defines the [MountpointIndicator] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+/// The flag showing whether to mark the directories on which another filesystem is mounted.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct MountpointIndicator(pub bool);
+
+impl Configurable<Self> for MountpointIndicator {
+    /// Get a potential `MountpointIndicator` value from [Cli].
+    ///
+    /// If the "mountpoint-indicator" argument is passed, this returns a `MountpointIndicator` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.mountpoint_indicator {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `MountpointIndicator` value from a [Config].
+    ///
+    /// If the `Config::mountpoint-indicator` has value,
+    /// this returns it as the value of the `MountpointIndicator`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.mountpoint_indicator.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::MountpointIndicator;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, MountpointIndicator::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_true() {
+        let argv = ["lsd", "--mountpoint-indicator"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            Some(MountpointIndicator(true)),
+            MountpointIndicator::from_cli(&cli)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, MountpointIndicator::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.mountpoint_indicator = Some(true);
+        assert_eq!(
+            Some(MountpointIndicator(true)),
+            MountpointIndicator::from_config(&c)
+        );
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.mountpoint_indicator = Some(false);
+        assert_eq!(
+            Some(MountpointIndicator(false)),
+            MountpointIndicator::from_config(&c)
+        );
+    }
+}
@@ -0,0 +1,85 @@
+//! This module defines the [OneFileSystem] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+/// The flag showing whether to stay on the filesystem of the listed paths when recursing.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct OneFileSystem(pub bool);
+
+impl Configurable<Self> for OneFileSystem {
+    /// Get a potential `OneFileSystem` value from [Cli].
+    ///
+    /// If the "one-file-system" argument is passed, this returns a `OneFileSystem` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.one_file_system {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `OneFileSystem` value from a [Config].
+    ///
+    /// If the `Config::one-file-system` has value,
+    /// this returns it as the value of the `OneFileSystem`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.one_file_system.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::OneFileSystem;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, OneFileSystem::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_true() {
+        let argv = ["lsd", "--one-file-system"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(OneFileSystem(true)), OneFileSystem::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_short() {
+        let argv = ["lsd", "-x"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(OneFileSystem(true)), OneFileSystem::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, OneFileSystem::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.one_file_system = Some(true);
+        assert_eq!(Some(OneFileSystem(true)), OneFileSystem::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.one_file_system = Some(false);
+        assert_eq!(Some(OneFileSystem(false)), OneFileSystem::from_config(&c));
+    }
+}
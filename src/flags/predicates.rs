@@ -0,0 +1,197 @@
+//! This module defines the [Predicates] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Predicates::configure_from) method.
+
+use crate::app::Cli;
+use crate::config_file::{self, Config};
+use crate::meta::{Date, Size};
+
+use chrono::{DateTime, Local};
+use clap::Error;
+use clap::error::ErrorKind;
+
+/// The conditions on the size, date and owner the listed entries have to meet.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Predicates {
+    /// The minimum size in bytes.
+    pub min_size: Option<u64>,
+    /// The maximum size in bytes.
+    pub max_size: Option<u64>,
+    /// The timestamp selected by `--time` has to be after this time.
+    pub newer: Option<DateTime<Local>>,
+    /// The timestamp selected by `--time` has to be before this time.
+    pub older: Option<DateTime<Local>>,
+    pub user: Option<OwnerId>,
+    pub group: Option<OwnerId>,
+}
+
+/// A user or a group of the `--user` and `--group` conditions, resolved to its id on unix and
+/// compared by name elsewhere.
+#[cfg(unix)]
+pub type OwnerId = u32;
+#[cfg(not(unix))]
+pub type OwnerId = String;
+
+impl Predicates {
+    /// Returns a value from either [Cli], a [Config] or a [Default] value. Each condition is
+    /// taken from [Cli] when passed there, and from the [Config] otherwise.
+    ///
+    /// # Errors
+    ///
+    /// If a size or a time cannot be parsed, or if the user or the group does not exist.
+    pub fn configure_from(cli: &Cli, config: &Config) -> Result<Self, Error> {
+        let value = |cli: &Option<String>, get: fn(&config_file::Predicates) -> &Option<String>| {
+            cli.clone()
+                .or_else(|| config.predicates.as_ref().and_then(|c| get(c).clone()))
+        };
+
+        Ok(Self {
+            min_size: Self::size(value(&cli.min_size, |c| &c.min_size), "min-size")?,
+            max_size: Self::size(value(&cli.max_size, |c| &c.max_size), "max-size")?,
+            newer: Self::time(value(&cli.newer, |c| &c.newer), "newer")?,
+            older: Self::time(value(&cli.older, |c| &c.older), "older")?,
+            user: Self::owner(value(&cli.user, |c| &c.user), "user")?,
+            group: Self::owner(value(&cli.group, |c| &c.group), "group")?,
+        })
+    }
+
+    /// Whether any condition is set.
+    pub fn is_active(&self) -> bool {
+        self != &Self::default()
+    }
+
+    fn size(value: Option<String>, name: &str) -> Result<Option<u64>, Error> {
+        value
+            .map(|value| {
+                Size::parse_bytes(&value).ok_or_else(|| {
+                    Error::raw(
+                        ErrorKind::ValueValidation,
+                        format!(
+                            "invalid {name} '{value}', expected a size like 512, 10K or 1.5M\n"
+                        ),
+                    )
+                })
+            })
+            .transpose()
+    }
+
+    /// Resolve the name of a user or a group, also given as an id on unix, so that a typo is
+    /// not mistaken for a listing without matches.
+    #[cfg(unix)]
+    fn owner(value: Option<String>, name: &str) -> Result<Option<OwnerId>, Error> {
+        value
+            .map(|value| {
+                let id = match name {
+                    "user" => users::get_user_by_name(&value).map(|user| user.uid()),
+                    _ => users::get_group_by_name(&value).map(|group| group.gid()),
+                };
+                id.or_else(|| value.parse().ok()).ok_or_else(|| {
+                    Error::raw(
+                        ErrorKind::ValueValidation,
+                        format!("unknown {name} '{value}'\n"),
+                    )
+                })
+            })
+            .transpose()
+    }
+
+    #[cfg(not(unix))]
+    fn owner(value: Option<String>, _name: &str) -> Result<Option<OwnerId>, Error> {
+        Ok(value)
+    }
+
+    fn time(value: Option<String>, name: &str) -> Result<Option<DateTime<Local>>, Error> {
+        value
+            .map(|value| {
+                Date::parse_time(&value).ok_or_else(|| {
+                    Error::raw(
+                        ErrorKind::ValueValidation,
+                        format!(
+                            "invalid {name} '{value}', expected a time like 2d or 2025-01-01\n"
+                        ),
+                    )
+                })
+            })
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+    use clap::error::ErrorKind;
+
+    use super::Predicates;
+
+    use crate::app::Cli;
+    use crate::config_file::{self, Config};
+
+    #[test]
+    fn test_configure_from_default() {
+        let cli = Cli::try_parse_from(["lsd"]).unwrap();
+        let predicates = Predicates::configure_from(&cli, &Config::with_none()).unwrap();
+        assert_eq!(Predicates::default(), predicates);
+        assert!(!predicates.is_active());
+    }
+
+    #[test]
+    fn test_configure_from_cli() {
+        let argv = [
+            "lsd",
+            "--min-size",
+            "1K",
+            "--max-size",
+            "2M",
+            "--older",
+            "2025-01-01",
+            "--user",
+            "root",
+        ];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let predicates = Predicates::configure_from(&cli, &Config::with_none()).unwrap();
+        assert_eq!(Some(1024), predicates.min_size);
+        assert_eq!(Some(2 * 1024 * 1024), predicates.max_size);
+        assert_eq!(
+            "2025-01-01",
+            predicates.older.unwrap().format("%F").to_string()
+        );
+        assert_eq!(None, predicates.newer);
+        #[cfg(unix)]
+        assert_eq!(Some(0), predicates.user);
+        assert!(predicates.is_active());
+    }
+
+    #[test]
+    fn test_configure_from_cli_over_config() {
+        let cli = Cli::try_parse_from(["lsd", "--min-size", "1K"]).unwrap();
+        let mut c = Config::with_none();
+        c.predicates = Some(config_file::Predicates {
+            min_size: Some("1M".to_string()),
+            max_size: None,
+            newer: None,
+            older: None,
+            user: None,
+            group: Some("0".to_string()),
+        });
+        let predicates = Predicates::configure_from(&cli, &c).unwrap();
+        assert_eq!(Some(1024), predicates.min_size);
+        #[cfg(unix)]
+        assert_eq!(Some(0), predicates.group);
+    }
+
+    #[test]
+    fn test_configure_from_invalid() {
+        let cli = Cli::try_parse_from(["lsd", "--newer", "soon"]).unwrap();
+        let err = Predicates::configure_from(&cli, &Config::with_none()).unwrap_err();
+        assert_eq!(ErrorKind::ValueValidation, err.kind());
+
+        let cli = Cli::try_parse_from(["lsd", "--max-size", "big"]).unwrap();
+        assert!(Predicates::configure_from(&cli, &Config::with_none()).is_err());
+
+        #[cfg(unix)]
+        {
+            let cli = Cli::try_parse_from(["lsd", "--user", "no-such-user"]).unwrap();
+            let err = Predicates::configure_from(&cli, &Config::with_none()).unwrap_err();
+            assert_eq!(ErrorKind::ValueValidation, err.kind());
+        }
+    }
+}
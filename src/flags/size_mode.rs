@@ -0,0 +1,98 @@
+//! This module defines the [SizeMode] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+use serde::Deserialize;
+
+/// The flag showing which file size to display and sort by.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum SizeMode {
+    /// The variant to use the length of the file, like `du --apparent-size`.
+    #[default]
+    Apparent,
+    /// The variant to use the space allocated on the disk, like `du`.
+    Allocated,
+}
+
+impl SizeMode {
+    fn from_arg_str(value: &str) -> Self {
+        match value {
+            "apparent" => Self::Apparent,
+            "allocated" => Self::Allocated,
+            // Invalid value should be handled by `clap` when building an `Cli`
+            other => unreachable!("Invalid value '{other}' for 'size-mode'"),
+        }
+    }
+}
+
+impl Configurable<Self> for SizeMode {
+    /// Get a potential `SizeMode` variant from [Cli].
+    ///
+    /// If the "size-mode" argument is passed, the corresponding `SizeMode` variant is returned
+    /// in a [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        cli.size_mode.as_deref().map(Self::from_arg_str)
+    }
+
+    /// Get a potential `SizeMode` variant from a [Config].
+    ///
+    /// If the `Config::size-mode` has value, this returns it in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.size_mode
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::SizeMode;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_default() {
+        assert_eq!(SizeMode::Apparent, SizeMode::default());
+    }
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, SizeMode::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_allocated() {
+        let argv = ["lsd", "--size-mode", "allocated"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(SizeMode::Allocated), SizeMode::from_cli(&cli));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_cli_unknown() {
+        let argv = ["lsd", "--size-mode", "compressed"];
+        let _ = Cli::try_parse_from(argv).unwrap();
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, SizeMode::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_allocated() {
+        let mut c = Config::with_none();
+        c.size_mode = Some(SizeMode::Allocated);
+        assert_eq!(Some(SizeMode::Allocated), SizeMode::from_config(&c));
+    }
+}
@@ -0,0 +1,75 @@
+//! This module defines the [Sniff] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+/// The flag showing whether to guess the type of regular files from their first bytes, to pick
+/// the icon and color of the files whose name is not enough.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Sniff(pub bool);
+
+impl Configurable<Self> for Sniff {
+    /// Get a potential `Sniff` value from [Cli].
+    ///
+    /// If the "sniff" argument is passed, this returns a `Sniff` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.sniff { Some(Self(true)) } else { None }
+    }
+
+    /// Get a potential `Sniff` value from a [Config].
+    ///
+    /// If the `Config::sniff` has value,
+    /// this returns it as the value of the `Sniff`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.sniff.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::Sniff;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, Sniff::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_true() {
+        let argv = ["lsd", "--sniff"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(Sniff(true)), Sniff::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Sniff::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.sniff = Some(true);
+        assert_eq!(Some(Sniff(true)), Sniff::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.sniff = Some(false);
+        assert_eq!(Some(Sniff(false)), Sniff::from_config(&c));
+    }
+}
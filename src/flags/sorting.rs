@@ -45,6 +45,7 @@ pub enum SortColumn {
     Size,
     Version,
     GitStatus,
+    Hash,
 }
 
 impl Configurable<Self> for SortColumn {
@@ -65,6 +66,8 @@ impl Configurable<Self> for SortColumn {
             Some(Self::Version)
         } else if cli.gitsort || sort == Some("git") {
             Some(Self::GitStatus)
+        } else if sort == Some("hash") {
+            Some(Self::Hash)
         } else if cli.no_sort || sort == Some("none") {
             Some(Self::None)
         } else {
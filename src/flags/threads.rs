@@ -0,0 +1,97 @@
+//! This module defines the [Threads] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+/// The flag showing how many threads to use when walking directories.
+///
+/// A value of `1` keeps the traversal sequential, `0` lets the thread pool pick one thread per
+/// available CPU.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub struct Threads(pub usize);
+
+impl Threads {
+    /// Whether the directories should be walked by a thread pool.
+    pub fn is_parallel(&self) -> bool {
+        self.0 != 1
+    }
+}
+
+impl Configurable<Self> for Threads {
+    /// Get a potential `Threads` value from [Cli].
+    ///
+    /// If the "threads" argument is passed, this returns a `Threads` with its value in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        cli.threads.map(Self)
+    }
+
+    /// Get a potential `Threads` value from a [Config].
+    ///
+    /// If the `Config::threads` has value,
+    /// this returns it as the value of the `Threads`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.threads.map(Self)
+    }
+}
+
+/// The default value for `Threads` is a sequential traversal.
+impl Default for Threads {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::Threads;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_default() {
+        assert!(!Threads::default().is_parallel());
+    }
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, Threads::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_some() {
+        let argv = ["lsd", "--threads", "4"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(Threads(4)), Threads::from_cli(&cli));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_cli_invalid() {
+        let argv = ["lsd", "--threads", "many"];
+        let _ = Cli::try_parse_from(argv).unwrap();
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Threads::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_some() {
+        let mut c = Config::with_none();
+        c.threads = Some(0);
+        assert_eq!(Some(Threads(0)), Threads::from_config(&c));
+        assert!(Threads(0).is_parallel());
+    }
+}
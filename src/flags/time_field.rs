@@ -0,0 +1,153 @@
+//! This module defines the [TimeField] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+use serde::Deserialize;
+
+/// The flag showing which timestamp to display, sort and filter by.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Default)]
+pub enum TimeField {
+    /// The variant to use the last modification of the content.
+    #[default]
+    #[serde(rename = "mtime")]
+    Modified,
+    /// The variant to use the last access.
+    #[serde(rename = "atime")]
+    Accessed,
+    /// The variant to use the last change of the content or of the metadata.
+    #[serde(rename = "ctime")]
+    Changed,
+    /// The variant to use the creation, when supported by the filesystem.
+    #[serde(rename = "btime")]
+    Birth,
+}
+
+impl TimeField {
+    /// Get the `TimeField` from one of the words accepted by GNU `ls --time`.
+    pub fn from_arg_str(value: &str) -> Option<Self> {
+        match value {
+            "mtime" | "modification" => Some(Self::Modified),
+            "atime" | "access" | "use" => Some(Self::Accessed),
+            "ctime" | "status" => Some(Self::Changed),
+            "btime" | "birth" | "creation" => Some(Self::Birth),
+            _ => None,
+        }
+    }
+
+    /// The title of the date column showing this timestamp.
+    pub fn header(&self) -> &'static str {
+        match self {
+            Self::Modified => "Date Modified",
+            Self::Accessed => "Date Accessed",
+            Self::Changed => "Date Changed",
+            Self::Birth => "Date Created",
+        }
+    }
+}
+
+impl Configurable<Self> for TimeField {
+    /// Get a potential `TimeField` variant from [Cli].
+    ///
+    /// If the "time" argument is passed, the corresponding `TimeField` variant is returned in a
+    /// [Some]. If the "u" or "c" arguments are passed, this returns the access or change time
+    /// respectively in a [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if let Some(time) = cli.time.as_deref() {
+            // Invalid value should be handled by `clap` when building an `Cli`
+            Self::from_arg_str(time)
+        } else if cli.access_time {
+            Some(Self::Accessed)
+        } else if cli.change_time {
+            Some(Self::Changed)
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `TimeField` variant from a [Config].
+    ///
+    /// If the `Config::time` has value, this returns it in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.time
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::TimeField;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_default() {
+        assert_eq!(TimeField::Modified, TimeField::default());
+    }
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, TimeField::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_time() {
+        let argv = ["lsd", "--time", "ctime"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(TimeField::Changed), TimeField::from_cli(&cli));
+
+        let argv = ["lsd", "--time=birth"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(TimeField::Birth), TimeField::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_short() {
+        let argv = ["lsd", "-u"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(TimeField::Accessed), TimeField::from_cli(&cli));
+
+        let argv = ["lsd", "-c"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(TimeField::Changed), TimeField::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_last_wins() {
+        let argv = ["lsd", "-u", "--time", "mtime"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(TimeField::Modified), TimeField::from_cli(&cli));
+
+        let argv = ["lsd", "--time", "mtime", "-c"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(TimeField::Changed), TimeField::from_cli(&cli));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_cli_unknown() {
+        let argv = ["lsd", "--time", "dtime"];
+        let _ = Cli::try_parse_from(argv).unwrap();
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, TimeField::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_atime() {
+        let mut c = Config::with_none();
+        c.time = Some(TimeField::Accessed);
+        assert_eq!(Some(TimeField::Accessed), TimeField::from_config(&c));
+    }
+}
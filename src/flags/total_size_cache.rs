@@ -0,0 +1,78 @@
+//! This module defines the [TotalSizeCache] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+
+/// The flag showing whether to keep the total size of directories in an on-disk cache.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct TotalSizeCache(pub bool);
+
+impl Configurable<Self> for TotalSizeCache {
+    /// Get a potential `TotalSizeCache` value from [Cli].
+    ///
+    /// If the "total-size-cache" argument is passed, this returns a `TotalSizeCache` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.total_size_cache {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `TotalSizeCache` value from a [Config].
+    ///
+    /// If the `Config::total-size-cache` has value,
+    /// this returns it as the value of the `TotalSizeCache`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.total_size_cache.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::TotalSizeCache;
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, TotalSizeCache::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_true() {
+        let argv = ["lsd", "--total-size-cache"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(Some(TotalSizeCache(true)), TotalSizeCache::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, TotalSizeCache::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.total_size_cache = Some(true);
+        assert_eq!(Some(TotalSizeCache(true)), TotalSizeCache::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.total_size_cache = Some(false);
+        assert_eq!(Some(TotalSizeCache(false)), TotalSizeCache::from_config(&c));
+    }
+}
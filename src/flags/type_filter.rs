@@ -0,0 +1,171 @@
+//! This module defines the [TypeFilter] flag. To set it up from [Cli], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app::Cli;
+use crate::config_file::Config;
+use crate::meta::FileType;
+
+use serde::Deserialize;
+
+/// A kind of entry to list, named like the types of `find -type`.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize)]
+pub enum EntryKind {
+    #[serde(rename = "f")]
+    File,
+    #[serde(rename = "d")]
+    Directory,
+    #[serde(rename = "l")]
+    SymLink,
+    #[serde(rename = "p")]
+    Pipe,
+    #[serde(rename = "s")]
+    Socket,
+    #[serde(rename = "b")]
+    BlockDevice,
+    #[serde(rename = "c")]
+    CharDevice,
+    /// An executable regular file.
+    #[serde(rename = "x")]
+    Executable,
+}
+
+impl EntryKind {
+    fn from_arg_str(value: &str) -> Self {
+        match value {
+            "f" => Self::File,
+            "d" => Self::Directory,
+            "l" => Self::SymLink,
+            "p" => Self::Pipe,
+            "s" => Self::Socket,
+            "b" => Self::BlockDevice,
+            "c" => Self::CharDevice,
+            "x" => Self::Executable,
+            // Invalid value should be handled by `clap` when building an `Cli`
+            other => unreachable!("Invalid value '{other}' for 'type'"),
+        }
+    }
+
+    fn matches(self, file_type: FileType) -> bool {
+        matches!(
+            (self, file_type),
+            (Self::File, FileType::File { .. })
+                | (Self::Directory, FileType::Directory { .. })
+                | (Self::SymLink, FileType::SymLink { .. })
+                | (Self::Pipe, FileType::Pipe)
+                | (Self::Socket, FileType::Socket)
+                | (Self::BlockDevice, FileType::BlockDevice)
+                | (Self::CharDevice, FileType::CharDevice)
+                | (Self::Executable, FileType::File { exec: true, .. })
+        )
+    }
+}
+
+/// The flag showing which kinds of entries to list, all of them when empty.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct TypeFilter(pub Vec<EntryKind>);
+
+impl TypeFilter {
+    /// Whether an entry of this type should be listed.
+    pub fn matches(&self, file_type: FileType) -> bool {
+        self.0.is_empty() || self.0.iter().any(|kind| kind.matches(file_type))
+    }
+}
+
+impl Configurable<Self> for TypeFilter {
+    /// Get a potential `TypeFilter` value from [Cli].
+    ///
+    /// If the "type" argument is passed, this returns a `TypeFilter` with the given kinds in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        if cli.r#type.is_empty() {
+            None
+        } else {
+            Some(Self(
+                cli.r#type
+                    .iter()
+                    .map(|kind| EntryKind::from_arg_str(kind))
+                    .collect(),
+            ))
+        }
+    }
+
+    /// Get a potential `TypeFilter` value from a [Config].
+    ///
+    /// If the `Config::types` has value, this returns it as the value of the `TypeFilter`, in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.types.clone().map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::{EntryKind, TypeFilter};
+
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+    use crate::meta::FileType;
+
+    #[test]
+    fn test_default_matches_everything() {
+        assert!(TypeFilter::default().matches(FileType::Pipe));
+    }
+
+    #[test]
+    fn test_from_cli_none() {
+        let argv = ["lsd"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(None, TypeFilter::from_cli(&cli));
+    }
+
+    #[test]
+    fn test_from_cli_multiple() {
+        let argv = ["lsd", "--type", "d,x", "--type", "l"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        assert_eq!(
+            Some(TypeFilter(vec![
+                EntryKind::Directory,
+                EntryKind::Executable,
+                EntryKind::SymLink
+            ])),
+            TypeFilter::from_cli(&cli)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_cli_unknown() {
+        let argv = ["lsd", "--type", "z"];
+        let _ = Cli::try_parse_from(argv).unwrap();
+    }
+
+    #[test]
+    fn test_from_config() {
+        let mut c = Config::with_none();
+        c.types = Some(vec![EntryKind::File]);
+        assert_eq!(
+            Some(TypeFilter(vec![EntryKind::File])),
+            TypeFilter::from_config(&c)
+        );
+    }
+
+    #[test]
+    fn test_matches() {
+        let filter = TypeFilter(vec![EntryKind::Executable, EntryKind::Directory]);
+        assert!(filter.matches(FileType::Directory { uid: false }));
+        assert!(filter.matches(FileType::File {
+            uid: false,
+            exec: true
+        }));
+        assert!(!filter.matches(FileType::File {
+            uid: false,
+            exec: false
+        }));
+        assert!(!filter.matches(FileType::SymLink { is_dir: true }));
+    }
+}
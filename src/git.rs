@@ -1,8 +1,17 @@
 use crate::meta::git_file_status::GitFileStatus;
+use crate::meta::{GitDiffStat, Meta, RepoSummary};
+use serde::Serialize;
 use std::path::{Path, PathBuf};
+#[cfg(not(feature = "no-git"))]
+use {
+    crate::meta::GitCommit,
+    std::collections::{BTreeMap, HashMap, HashSet},
+    std::sync::{Mutex, OnceLock},
+};
 
 #[allow(dead_code)]
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum GitStatus {
     /// No status info
     #[default]
@@ -23,13 +32,39 @@ pub enum GitStatus {
     Renamed,
     /// Entry content changed between old and new
     Modified,
+    /// Submodule whose checked out commit is not the one recorded in the superproject
+    NewCommits,
+    /// Submodule that is not checked out
+    Uninitialized,
     /// Entry in the index is conflicted
     Conflicted,
 }
 
+/// The Git status of a path, and the reduction of the statuses of everything under it.
+#[cfg(not(feature = "no-git"))]
+#[derive(Default)]
+struct StatusNode {
+    /// The status of the path itself, when Git reports one.
+    status: Option<git2::Status>,
+    /// The reduction of the statuses of the path and of all the paths under it.
+    aggregate: GitFileStatus,
+}
+
 pub struct GitCache {
+    /// The statuses by canonicalized path, with a node for each of their ancestors, so that the
+    /// status of any file or directory is a single lookup.
+    #[cfg(not(feature = "no-git"))]
+    statuses: BTreeMap<PathBuf, StatusNode>,
+    /// The repository and its canonicalized workdir, to walk the history.
+    #[cfg(not(feature = "no-git"))]
+    repo: Option<(Mutex<git2::Repository>, PathBuf)>,
+    /// The status of each submodule, by its canonicalized path.
     #[cfg(not(feature = "no-git"))]
-    statuses: Vec<(PathBuf, git2::Status)>,
+    submodules: Vec<(PathBuf, git2::SubmoduleStatus)>,
+    /// The line counts of the changes since HEAD by canonicalized path, summed in a node for
+    /// each of their ancestors. They are only read for the git-diff block.
+    #[cfg(not(feature = "no-git"))]
+    diff_stats: OnceLock<BTreeMap<PathBuf, GitDiffStat>>,
 }
 
 #[cfg(feature = "no-git")]
@@ -41,6 +76,42 @@ impl GitCache {
     pub fn get(&self, _filepath: &PathBuf, _is_directory: bool) -> Option<GitFileStatus> {
         None
     }
+
+    pub fn get_canonical(&self, _path: &Path, _is_directory: bool) -> Option<GitFileStatus> {
+        None
+    }
+
+    pub fn nested(&self, _dir: &Path) -> Option<GitCache> {
+        None
+    }
+
+    pub fn is_submodule(&self, _path: &Path) -> bool {
+        false
+    }
+
+    pub fn is_ignored(&self, _path: &Path) -> bool {
+        false
+    }
+
+    pub fn read_last_commits(&self, _dir: &Path, _metas: &mut [Meta]) {}
+
+    pub fn read_arguments_last_commits(
+        &self,
+        _dir: &Path,
+        _metas: &mut [Meta],
+        _arguments: &[(usize, String)],
+    ) {
+    }
+
+    pub fn summary(&self) -> Option<RepoSummary> {
+        None
+    }
+
+    pub fn diff_stat(&self, _path: &Path) -> Option<GitDiffStat> {
+        None
+    }
+
+    pub fn read_diff_stat(&self, _meta: &mut Meta) {}
 }
 
 #[cfg(not(feature = "no-git"))]
@@ -55,9 +126,16 @@ impl GitCache {
         };
 
         if let Some(workdir) = repo.workdir().and_then(|x| std::fs::canonicalize(x).ok()) {
-            let mut statuses = Vec::new();
-            // Retrieving Git statuses for workdir
-            match repo.statuses(None) {
+            let mut statuses = BTreeMap::new();
+            // Retrieving Git statuses for workdir, the ignored directories being listed as a
+            // whole rather than walked
+            let mut options = git2::StatusOptions::new();
+            options
+                .include_untracked(true)
+                .recurse_untracked_dirs(true)
+                .include_ignored(true)
+                .recurse_ignored_dirs(false);
+            match repo.statuses(Some(&mut options)) {
                 Ok(status_list) => {
                     for status_entry in status_list.iter() {
                         // git2-rs returns `None` from `.path()` when the filename
@@ -70,9 +148,12 @@ impl GitCache {
                         // git2-rs provides / separated path even on Windows. We have to rebuild it
                         let path: PathBuf =
                             str_path.split('/').collect::<Vec<_>>().iter().collect();
-                        let path = workdir.join(path);
-                        let elem = (path, status_entry.status());
-                        statuses.push(elem);
+                        Self::insert_status(
+                            &mut statuses,
+                            &workdir,
+                            workdir.join(path),
+                            status_entry.status(),
+                        );
                     }
                 }
                 Err(err) => {
@@ -84,7 +165,12 @@ impl GitCache {
                 }
             }
 
-            GitCache { statuses }
+            GitCache {
+                statuses,
+                submodules: Self::read_submodules(&repo, &workdir),
+                repo: Some((Mutex::new(repo), workdir)),
+                diff_stats: OnceLock::new(),
+            }
         } else {
             // No workdir
             Self::empty()
@@ -93,13 +179,99 @@ impl GitCache {
 
     pub fn empty() -> Self {
         GitCache {
-            statuses: Vec::new(),
+            statuses: BTreeMap::new(),
+            repo: None,
+            submodules: Vec::new(),
+            diff_stats: OnceLock::new(),
+        }
+    }
+
+    /// Store the status of `path`, and add it to the aggregates of the path and its ancestors
+    /// up to the `workdir`.
+    fn insert_status(
+        statuses: &mut BTreeMap<PathBuf, StatusNode>,
+        workdir: &Path,
+        path: PathBuf,
+        status: git2::Status,
+    ) {
+        let file_status = GitFileStatus::new(status);
+        // an ignored file leaves its directories clean, only an ignored directory is ignored
+        let ancestors = if status.contains(git2::Status::IGNORED) {
+            1
+        } else {
+            usize::MAX
+        };
+        for ancestor in path
+            .ancestors()
+            .take(ancestors)
+            .take_while(|ancestor| ancestor.starts_with(workdir))
+        {
+            let node: &mut StatusNode = statuses.entry(ancestor.to_path_buf()).or_default();
+            node.aggregate = GitFileStatus {
+                index: std::cmp::max(node.aggregate.index, file_status.index),
+                workdir: std::cmp::max(node.aggregate.workdir, file_status.workdir),
+            };
         }
+        statuses.entry(path).or_default().status = Some(status);
+    }
+
+    /// The status reported by Git for `path` itself.
+    fn own_status(&self, path: &Path) -> Option<git2::Status> {
+        self.statuses.get(path).and_then(|node| node.status)
+    }
+
+    fn read_submodules(
+        repo: &git2::Repository,
+        workdir: &Path,
+    ) -> Vec<(PathBuf, git2::SubmoduleStatus)> {
+        let Ok(submodules) = repo.submodules() else {
+            return Vec::new();
+        };
+        submodules
+            .iter()
+            .filter_map(|submodule| {
+                let status = repo
+                    .submodule_status(submodule.name()?, git2::SubmoduleIgnore::Unspecified)
+                    .ok()?;
+                Some((workdir.join(submodule.path()), status))
+            })
+            .collect()
+    }
+
+    /// A cache of its own for the directory `dir`, when it is the root of a submodule or of
+    /// another repository than the one of `self`.
+    ///
+    /// The statuses of `self` stop at such directories, so they are opened as the listing
+    /// enters them.
+    pub fn nested(&self, dir: &Path) -> Option<GitCache> {
+        // a submodule has a `.git` file, a nested repository a `.git` directory
+        if !dir.join(".git").exists() {
+            return None;
+        }
+        let dir = std::fs::canonicalize(dir).ok()?;
+        match &self.repo {
+            Some((_, workdir)) if *workdir == dir => None,
+            _ => Some(GitCache::new(&dir)),
+        }
+    }
+
+    /// Whether the canonicalized `path` is ignored, by itself or as part of an ignored
+    /// directory.
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        path.ancestors().any(|path| {
+            self.own_status(path)
+                .is_some_and(|status| status.contains(git2::Status::IGNORED))
+        })
+    }
+
+    /// Whether the canonicalized `path` is a submodule of the repository.
+    pub fn is_submodule(&self, path: &Path) -> bool {
+        self.submodules.iter().any(|(p, _)| p == path)
     }
 
     pub fn get(&self, filepath: &PathBuf, is_directory: bool) -> Option<GitFileStatus> {
         match std::fs::canonicalize(filepath) {
-            Ok(filename) => Some(self.inner_get(&filename, is_directory)),
+            Ok(filename) => self.get_canonical(&filename, is_directory),
             Err(err) => {
                 if err.kind() != std::io::ErrorKind::NotFound {
                     crate::print_error!("Cannot get git status for {:?}:  {}", filepath, err);
@@ -109,22 +281,261 @@ impl GitCache {
         }
     }
 
-    fn inner_get(&self, filepath: &PathBuf, is_directory: bool) -> GitFileStatus {
-        if is_directory {
-            self.statuses
-                .iter()
-                .filter(|&x| x.0.starts_with(filepath))
-                .map(|x| GitFileStatus::new(x.1))
-                .fold(GitFileStatus::default(), |acc, x| GitFileStatus {
-                    index: std::cmp::max(acc.index, x.index),
-                    workdir: std::cmp::max(acc.workdir, x.workdir),
-                })
-        } else {
-            self.statuses
+    /// The summary of the repository, or [None] outside of a repository.
+    ///
+    /// Its dirty state is the reduction of the statuses of all the files, leaving out the
+    /// ignored ones.
+    pub fn summary(&self) -> Option<RepoSummary> {
+        let (repo, _) = self.repo.as_ref()?;
+        let status = self
+            .statuses
+            .values()
+            .filter_map(|node| node.status)
+            .map(GitFileStatus::new)
+            .filter(|status| status.workdir != GitStatus::Ignored)
+            .reduce(|acc, status| GitFileStatus {
+                index: std::cmp::max(acc.index, status.index),
+                workdir: std::cmp::max(acc.workdir, status.workdir),
+            });
+
+        let mut repo = repo.lock().unwrap_or_else(|err| err.into_inner());
+        Some(RepoSummary::new(&mut repo, status))
+    }
+
+    /// The lines added and removed since HEAD in the file given by its canonicalized path, or
+    /// in all the files under the directory. Unchanged and untracked files have none.
+    pub fn diff_stat(&self, path: &Path) -> Option<GitDiffStat> {
+        self.diff_stats
+            .get_or_init(|| self.read_diff_stats())
+            .get(path)
+            .copied()
+    }
+
+    /// Set the line counts of a single file or directory, canonicalizing its path.
+    pub fn read_diff_stat(&self, meta: &mut Meta) {
+        if let Ok(path) = std::fs::canonicalize(&meta.path) {
+            meta.git_diff = self.diff_stat(&path);
+        }
+    }
+
+    /// Diff HEAD with the working tree through the index, like `git diff HEAD --stat`.
+    fn read_diff_stats(&self) -> BTreeMap<PathBuf, GitDiffStat> {
+        let mut stats = BTreeMap::new();
+        let Some((repo, workdir)) = &self.repo else {
+            return stats;
+        };
+        let repo = repo.lock().unwrap_or_else(|err| err.into_inner());
+
+        let diff = repo
+            .head()
+            .ok()
+            .map(|head| head.peel_to_tree())
+            .transpose()
+            .and_then(|head_tree| repo.diff_tree_to_workdir_with_index(head_tree.as_ref(), None));
+        let diff = match diff {
+            Ok(diff) => diff,
+            Err(err) => {
+                crate::print_error!("Cannot diff the Git HEAD of {:?}: {}", workdir, err);
+                return stats;
+            }
+        };
+
+        for idx in 0..diff.deltas().len() {
+            let Ok(Some(patch)) = git2::Patch::from_diff(&diff, idx) else {
+                continue;
+            };
+            let Ok((_, added, removed)) = patch.line_stats() else {
+                continue;
+            };
+            let delta = patch.delta();
+            let Some(str_path) = delta.new_file().path().or_else(|| delta.old_file().path()) else {
+                continue;
+            };
+            let stat = GitDiffStat::new(added, removed);
+            let path = workdir.join(str_path);
+            for ancestor in path
+                .ancestors()
+                .take_while(|ancestor| ancestor.starts_with(workdir))
+            {
+                *stats.entry(ancestor.to_path_buf()).or_default() += stat;
+            }
+        }
+        stats
+    }
+
+    /// Set the last commit of HEAD touching each of the `metas` listed in the directory `dir`.
+    ///
+    /// The history is walked once for the whole directory, comparing the tree of the directory
+    /// in each commit with the one of its parents.
+    pub fn read_last_commits(&self, dir: &Path, metas: &mut [Meta]) {
+        let names: HashSet<String> = metas
+            .iter()
+            .filter(|meta| meta.name.name != "." && meta.name.name != "..")
+            .filter_map(|meta| meta.path.file_name()?.to_str().map(String::from))
+            .collect();
+        if names.is_empty() {
+            return;
+        }
+        let Ok(dir) = std::fs::canonicalize(dir) else {
+            return;
+        };
+
+        let mut commits = self.last_commits(&dir, names);
+        for meta in metas {
+            if meta.name.name == "." || meta.name.name == ".." {
+                continue;
+            }
+            if let Some(name) = meta.path.file_name().and_then(|name| name.to_str()) {
+                meta.git_commit = commits.remove(name);
+            }
+        }
+    }
+
+    /// Set the last commit touching each of the path `arguments` listed in the canonicalized
+    /// directory `dir`, given by their index in `metas` and their name in `dir`.
+    ///
+    /// Like for the content of a directory, the history is walked once for all the arguments.
+    pub fn read_arguments_last_commits(
+        &self,
+        dir: &Path,
+        metas: &mut [Meta],
+        arguments: &[(usize, String)],
+    ) {
+        let names = arguments.iter().map(|(_, name)| name.clone()).collect();
+        let commits = self.last_commits(dir, names);
+        for (index, name) in arguments {
+            metas[*index].git_commit = commits.get(name).cloned();
+        }
+    }
+
+    /// The last commits touching the `names` of the canonicalized directory `dir`, leaving out
+    /// the names that are not tracked in HEAD.
+    fn last_commits(&self, dir: &Path, names: HashSet<String>) -> HashMap<String, GitCommit> {
+        let Some((repo, workdir)) = &self.repo else {
+            return HashMap::new();
+        };
+        let Ok(rel_dir) = dir.strip_prefix(workdir) else {
+            return HashMap::new();
+        };
+        let repo = repo.lock().unwrap_or_else(|err| err.into_inner());
+
+        let commits = repo
+            .head()
+            .and_then(|head| head.peel_to_commit())
+            .and_then(|head| Self::walk_last_commits(&repo, &head, rel_dir, names));
+        match commits {
+            Ok(commits) => commits,
+            Err(err) => {
+                // an unborn HEAD has no history to show
+                if err.code() != git2::ErrorCode::UnbornBranch {
+                    crate::print_error!("Cannot walk the Git history of {:?}: {}", dir, err);
+                }
+                HashMap::new()
+            }
+        }
+    }
+
+    /// The last commits reachable from `head` touching the `names` of the directory `rel_dir`,
+    /// given relatively to the workdir.
+    pub fn walk_last_commits(
+        repo: &git2::Repository,
+        head: &git2::Commit,
+        rel_dir: &Path,
+        names: HashSet<String>,
+    ) -> Result<HashMap<String, GitCommit>, git2::Error> {
+        let Some(head_tree) = Self::dir_tree(repo, head, rel_dir) else {
+            return Ok(HashMap::new());
+        };
+        let mut pending: HashSet<String> = names
+            .into_iter()
+            .filter(|name| head_tree.get_name(name).is_some())
+            .collect();
+
+        let mut commits = HashMap::new();
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(head.id())?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+
+        for oid in revwalk {
+            if pending.is_empty() {
+                break;
+            }
+            let commit = repo.find_commit(oid?)?;
+            let Some(tree) = Self::dir_tree(repo, &commit, rel_dir) else {
+                continue;
+            };
+            let parent_trees: Vec<Option<git2::Tree>> = commit
+                .parents()
+                .map(|parent| Self::dir_tree(repo, &parent, rel_dir))
+                .collect();
+            // nothing changed in the directory, or it came unchanged from a merged branch
+            if parent_trees
                 .iter()
-                .find(|&x| filepath == &x.0)
-                .map(|e| GitFileStatus::new(e.1))
-                .unwrap_or_default()
+                .flatten()
+                .any(|parent_tree| parent_tree.id() == tree.id())
+            {
+                continue;
+            }
+
+            pending.retain(|name| {
+                let Some(id) = tree.get_name(name).map(|entry| entry.id()) else {
+                    return true;
+                };
+                let changed = parent_trees.iter().all(|parent_tree| {
+                    parent_tree
+                        .as_ref()
+                        .and_then(|parent_tree| parent_tree.get_name(name))
+                        .is_none_or(|entry| entry.id() != id)
+                });
+                if changed {
+                    commits.insert(name.clone(), GitCommit::new(&commit));
+                }
+                !changed
+            });
+        }
+
+        Ok(commits)
+    }
+
+    /// The tree of the directory `rel_dir` in a commit, if the directory exists there.
+    fn dir_tree<'r>(
+        repo: &'r git2::Repository,
+        commit: &git2::Commit<'r>,
+        rel_dir: &Path,
+    ) -> Option<git2::Tree<'r>> {
+        let tree = commit.tree().ok()?;
+        if rel_dir.as_os_str().is_empty() {
+            return Some(tree);
+        }
+        tree.get_path(rel_dir)
+            .ok()?
+            .to_object(repo)
+            .ok()?
+            .into_tree()
+            .ok()
+    }
+
+    /// The status of a file or directory given by its canonicalized path, which the listing
+    /// builds once per directory rather than canonicalizing each entry.
+    pub fn get_canonical(&self, path: &Path, is_directory: bool) -> Option<GitFileStatus> {
+        Some(self.inner_get(path, is_directory))
+    }
+
+    fn inner_get(&self, filepath: &Path, is_directory: bool) -> GitFileStatus {
+        if let Some((_, status)) = self.submodules.iter().find(|(path, _)| path == filepath) {
+            return GitFileStatus::for_submodule(*status);
+        }
+        // the content of an ignored directory is not listed in the statuses
+        if let Some(status) = filepath.ancestors().skip(1).find_map(|path| {
+            self.own_status(path)
+                .filter(|status| status.contains(git2::Status::IGNORED))
+        }) {
+            return GitFileStatus::new(status);
+        }
+        match self.statuses.get(filepath) {
+            Some(node) if is_directory => node.aggregate,
+            Some(node) => node.status.map(GitFileStatus::new).unwrap_or_default(),
+            None => GitFileStatus::default(),
         }
     }
 }
@@ -200,6 +611,282 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_last_commits() {
+        let (root, repo) = repo_init();
+        let mut index = repo.index().unwrap();
+        root.child("a").write_str("a").unwrap();
+        root.child("d/b").write_str("b").unwrap();
+        index.add_path(Path::new("a")).unwrap();
+        index.add_path(Path::new("d/b")).unwrap();
+        let (first, _) = commit(&repo, &mut index, "Add files");
+        root.child("a").write_str("changed").unwrap();
+        index.add_path(Path::new("a")).unwrap();
+        let (second, _) = commit(&repo, &mut index, "Change a");
+        root.child("untracked").touch().unwrap();
+
+        let flags = crate::flags::Flags::default();
+        let mut meta =
+            Meta::from_path(root.path(), false, crate::flags::PermissionFlag::Rwx).unwrap();
+        let cache = GitCache::new(root.path());
+        let mut content = meta.recurse_into(1, &flags, None, None).unwrap().0.unwrap();
+        cache.read_last_commits(root.path(), &mut content);
+
+        let commit_of = |name: &str| {
+            content
+                .iter()
+                .find(|meta| meta.name.name == name)
+                .and_then(|meta| meta.git_commit.as_ref())
+                .map(|commit| commit.id().to_string())
+        };
+        assert_eq!(Some(second.to_string()), commit_of("a"));
+        assert_eq!(Some(first.to_string()), commit_of("d"));
+        assert_eq!(None, commit_of("untracked"));
+
+        let mut files: Vec<Meta> = ["d/b", "d/b", "a"]
+            .iter()
+            .map(|path| {
+                Meta::from_path(
+                    &root.path().join(path),
+                    false,
+                    crate::flags::PermissionFlag::Rwx,
+                )
+                .unwrap()
+            })
+            .collect();
+        let dir = std::fs::canonicalize(root.path().join("d")).unwrap();
+        cache.read_arguments_last_commits(
+            &dir,
+            &mut files,
+            &[(0, "b".to_string()), (1, "b".to_string())],
+        );
+        let commits: Vec<Option<String>> = files
+            .iter()
+            .map(|file| file.git_commit.as_ref().map(|c| c.id().to_string()))
+            .collect();
+        assert_eq!(
+            vec![Some(first.to_string()), Some(first.to_string()), None],
+            commits
+        );
+    }
+
+    #[test]
+    fn test_diff_stats() {
+        let (root, repo) = repo_init();
+        let mut index = repo.index().unwrap();
+        root.child("a").write_str("1\n2\n3\n").unwrap();
+        root.child("d/b").write_str("1\n").unwrap();
+        root.child("d/c").write_str("1\n2\n").unwrap();
+        for path in ["a", "d/b", "d/c"] {
+            index.add_path(Path::new(path)).unwrap();
+        }
+        commit(&repo, &mut index, "Add files");
+
+        // a change in the working tree, and a staged one
+        root.child("a").write_str("1\ntwo\n3\n4\n").unwrap();
+        root.child("d/b").write_str("1\n2\n").unwrap();
+        index.add_path(Path::new("d/b")).unwrap();
+        index.write().unwrap();
+        std::fs::remove_file(root.child("d/c").path()).unwrap();
+        root.child("untracked").write_str("1\n").unwrap();
+
+        let cache = GitCache::new(root.path());
+        let canonical = std::fs::canonicalize(root.path()).unwrap();
+        let stat = |path: &str| cache.diff_stat(&canonical.join(path));
+        assert_eq!(Some(GitDiffStat::new(2, 1)), stat("a"));
+        assert_eq!(Some(GitDiffStat::new(1, 0)), stat("d/b"));
+        assert_eq!(Some(GitDiffStat::new(1, 2)), stat("d"));
+        assert_eq!(Some(GitDiffStat::new(3, 3)), stat(""));
+        assert_eq!(None, stat("untracked"));
+    }
+
+    #[test]
+    fn test_ignored_directories() {
+        let (root, _repo) = repo_init();
+        root.child(".gitignore")
+            .write_str("target/\n*.log\n")
+            .unwrap();
+        root.child("target/debug/lsd").touch().unwrap();
+        root.child("build.log").touch().unwrap();
+        root.child("src/main.rs").touch().unwrap();
+
+        let cache = GitCache::new(root.path());
+        let ignored = GitFileStatus {
+            index: GitStatus::Unmodified,
+            workdir: GitStatus::Ignored,
+        };
+        let status = |path: &str, is_directory| {
+            let path = std::fs::canonicalize(root.child(path).path()).unwrap();
+            cache.inner_get(&path, is_directory)
+        };
+        assert_eq!(ignored, status("target", true));
+        assert_eq!(ignored, status("target/debug", true));
+        assert_eq!(ignored, status("target/debug/lsd", false));
+        assert_eq!(ignored, status("build.log", false));
+        assert_eq!(
+            GitStatus::NewInWorkdir,
+            status("src/main.rs", false).workdir
+        );
+
+        let canonical = |path: &str| std::fs::canonicalize(root.child(path).path()).unwrap();
+        assert!(cache.is_ignored(&canonical("target/debug")));
+        assert!(!cache.is_ignored(&canonical("src")));
+    }
+
+    #[test]
+    fn test_ignored_file_in_tracked_directory() {
+        let (root, repo) = repo_init();
+        let mut index = repo.index().unwrap();
+        root.child(".gitignore").write_str("*.log\n").unwrap();
+        root.child("lib/a.rs").touch().unwrap();
+        index.add_path(Path::new(".gitignore")).unwrap();
+        index.add_path(Path::new("lib/a.rs")).unwrap();
+        index.write().unwrap();
+        commit(&repo, &mut index, "Add lib");
+        root.child("lib/debug.log").touch().unwrap();
+
+        let cache = GitCache::new(root.path());
+        let lib = std::fs::canonicalize(root.child("lib").path()).unwrap();
+        assert_eq!(GitFileStatus::default(), cache.inner_get(&lib, true));
+        assert_eq!(
+            GitStatus::Ignored,
+            cache.inner_get(&lib.join("debug.log"), false).workdir
+        );
+    }
+
+    #[test]
+    fn test_statuses_stop_at_workdir() {
+        let workdir = PathBuf::from("/repo/sub");
+        let mut statuses = BTreeMap::new();
+        for path in ["a", "d/b", "d/c"] {
+            GitCache::insert_status(
+                &mut statuses,
+                &workdir,
+                workdir.join(path),
+                git2::Status::WT_MODIFIED,
+            );
+        }
+
+        let paths: Vec<&Path> = statuses.keys().map(PathBuf::as_path).collect();
+        assert_eq!(
+            vec![
+                Path::new("/repo/sub"),
+                Path::new("/repo/sub/a"),
+                Path::new("/repo/sub/d"),
+                Path::new("/repo/sub/d/b"),
+                Path::new("/repo/sub/d/c"),
+            ],
+            paths
+        );
+    }
+
+    /// The status of every directory of a large dirty repository is aggregated once, while the
+    /// statuses are read, so looking it up never walks the statuses under it.
+    #[test]
+    fn test_aggregates_in_large_repository() {
+        let workdir = PathBuf::from("/repo");
+        let mut statuses = BTreeMap::new();
+        for dir in 0..20 {
+            for file in 0..50 {
+                let path = workdir.join(format!("dir{dir}/sub{}/file{file}", file % 10));
+                GitCache::insert_status(&mut statuses, &workdir, path, git2::Status::WT_MODIFIED);
+            }
+        }
+        let staged = workdir.join("dir7/sub3/staged");
+        GitCache::insert_status(
+            &mut statuses,
+            &workdir,
+            staged.clone(),
+            git2::Status::INDEX_NEW,
+        );
+
+        // a node per file and per directory up to the workdir, and none above it
+        assert_eq!(20 * 50 + 1 + 20 * 10 + 20 + 1, statuses.len());
+        assert!(!statuses.contains_key(Path::new("/")));
+
+        let modified = GitFileStatus::new(git2::Status::WT_MODIFIED);
+        let both = GitFileStatus::new(git2::Status::WT_MODIFIED | git2::Status::INDEX_NEW);
+        let node = |path: &str| statuses[&workdir.join(path)].aggregate;
+        assert_eq!(both, node(""));
+        assert_eq!(both, node("dir7"));
+        assert_eq!(both, node("dir7/sub3"));
+        assert_eq!(modified, node("dir7/sub4"));
+        assert_eq!(modified, node("dir8"));
+        assert_eq!(None, statuses[&workdir.join("dir7")].status);
+
+        let expected: Vec<(PathBuf, GitFileStatus)> = statuses
+            .iter()
+            .map(|(path, node)| (path.clone(), node.aggregate))
+            .collect();
+        let cache = GitCache {
+            statuses,
+            repo: None,
+            submodules: Vec::new(),
+            diff_stats: OnceLock::new(),
+        };
+        // a directory is its aggregated node, a file its own status
+        for (path, aggregate) in expected {
+            assert_eq!(aggregate, cache.inner_get(&path, true), "{path:?}");
+        }
+        assert_eq!(
+            GitFileStatus::new(git2::Status::INDEX_NEW),
+            cache.inner_get(&staged, false)
+        );
+        assert_eq!(
+            GitFileStatus::default(),
+            cache.inner_get(&workdir.join("clean"), true)
+        );
+    }
+
+    #[test]
+    fn test_nested_repositories() {
+        let (root, repo) = repo_init();
+        let (lib, lib_repo) = repo_init();
+        lib.child("lib.rs").touch().unwrap();
+        let mut lib_index = lib_repo.index().unwrap();
+        lib_index.add_path(Path::new("lib.rs")).unwrap();
+        commit(&lib_repo, &mut lib_index, "Add lib");
+
+        let url = lib.path().to_str().unwrap();
+        let mut submodule = repo.submodule(url, Path::new("lib"), true).unwrap();
+        let sub_repo = submodule.clone(None).unwrap();
+        submodule.add_finalize().unwrap();
+        let mut index = repo.index().unwrap();
+        commit(&repo, &mut index, "Add submodule");
+
+        root.child("nested/file").touch().unwrap();
+        Repository::init(root.child("nested").path()).unwrap();
+
+        let cache = GitCache::new(root.path());
+        let sub_path = std::fs::canonicalize(root.child("lib").path()).unwrap();
+        assert!(cache.is_submodule(&sub_path));
+        assert!(!cache.is_submodule(&sub_path.with_file_name("nested")));
+        assert_eq!(Some(GitFileStatus::default()), cache.get(&sub_path, true));
+
+        // a commit in the submodule is not the one recorded by the superproject
+        let sig = t!(repo.signature());
+        let head = t!(sub_repo.head()).peel_to_commit().unwrap();
+        let tree = head.tree().unwrap();
+        t!(sub_repo.commit(Some("HEAD"), &sig, &sig, "More", &tree, &[&head]));
+        let cache = GitCache::new(root.path());
+        assert_eq!(
+            Some(GitStatus::NewCommits),
+            cache.get(&sub_path, true).map(|status| status.workdir)
+        );
+
+        // the files of the nested repository get their status from its own cache
+        let nested = root.child("nested").path().to_path_buf();
+        assert!(cache.nested(root.path()).is_none());
+        let nested_cache = cache.nested(&nested).unwrap();
+        assert_eq!(
+            Some(GitStatus::NewInWorkdir),
+            nested_cache
+                .get(&nested.join("file"), false)
+                .map(|status| status.workdir)
+        );
+        assert!(cache.nested(&sub_path).is_some());
+    }
+
     #[test]
     fn test_git_workflow() {
         // rename as test_git_workflow
@@ -285,9 +972,10 @@ mod tests {
 
         index.add_path(f1.as_path()).unwrap();
         index.write().unwrap();
+        // the ignored f2.bak does not make its directory ignored
         *expected_statuses.get_mut(&d1).unwrap() = GitFileStatus {
             index: GitStatus::NewInIndex,
-            workdir: GitStatus::Ignored,
+            workdir: GitStatus::Unmodified,
         };
         *expected_statuses.get_mut(&f1).unwrap() = GitFileStatus {
             index: GitStatus::NewInIndex,
@@ -24,6 +24,8 @@ impl GitTheme {
             GitStatus::Deleted => &self.symbols.deleted,
             GitStatus::Renamed => &self.symbols.renamed,
             GitStatus::Modified => &self.symbols.modified,
+            GitStatus::NewCommits => &self.symbols.new_commits,
+            GitStatus::Uninitialized => &self.symbols.uninitialized,
             GitStatus::Conflicted => &self.symbols.conflicted,
         };
         symbol.to_string()
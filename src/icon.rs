@@ -45,6 +45,7 @@ impl Icons {
                     FileType::CharDevice => &t.filetype.device_char,
                     FileType::BlockDevice => &t.filetype.device_block,
                     FileType::Special => &t.filetype.special,
+                    FileType::Directory { .. } if name.is_submodule() => &t.filetype.submodule,
                     _ => {
                         if let Some(icon) = t.name.get(name.file_name().to_lowercase().as_str()) {
                             icon
@@ -53,6 +54,13 @@ impl Icons {
                             .and_then(|ext| t.extension.get(ext.to_lowercase().as_str()))
                         {
                             icon
+                        } else if let Some(icon) = name.mime().and_then(|mime| {
+                            t.mime
+                                .get(mime.essence())
+                                .or_else(|| t.mime.get(mime.category()))
+                                .or_else(|| mime.extension().and_then(|ext| t.extension.get(ext)))
+                        }) {
+                            icon
                         } else {
                             match file_type {
                                 FileType::Directory { .. } => &t.filetype.dir,
@@ -76,7 +84,7 @@ impl Icons {
 mod test {
     use super::{IconTheme, Icons};
     use crate::flags::{IconOption, IconTheme as FlagTheme, PermissionFlag};
-    use crate::meta::Meta;
+    use crate::meta::{Meta, Mime};
     use std::fs::File;
     use tempfile::tempdir;
 
@@ -234,4 +242,30 @@ mod test {
             assert_eq!(icon_str, format!("{}{}", file_icon, icon.icon_separator));
         }
     }
+
+    #[test]
+    fn get_icon_by_mime() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("run");
+        std::fs::write(&file_path, "#!/usr/bin/env python3\n").expect("failed to write file");
+        let mut meta = Meta::from_path(&file_path, false, PermissionFlag::Rwx).unwrap();
+
+        let icons = Icons::new(true, IconOption::Always, FlagTheme::Fancy, " ".to_string());
+        assert_eq!("\u{f016} ", icons.get(&meta.name));
+
+        meta.name.set_mime(Mime::sniff(&meta.path, meta.file_type));
+        assert_eq!("\u{e606} ", icons.get(&meta.name));
+    }
+
+    #[test]
+    fn get_icon_submodule() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let mut meta = Meta::from_path(tmp_dir.path(), false, PermissionFlag::Rwx).unwrap();
+
+        let icons = Icons::new(true, IconOption::Always, FlagTheme::Fancy, " ".to_string());
+        assert_eq!("\u{f115} ", icons.get(&meta.name));
+
+        meta.name.set_submodule(true);
+        assert_eq!("\u{f414} ", icons.get(&meta.name));
+    }
 }
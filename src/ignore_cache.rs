@@ -0,0 +1,298 @@
+//! This module provides the [IgnoreCache], deciding which paths are ignored by the `.gitignore`
+//! and `.ignore` files of a tree.
+
+use ignore::Match;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::collections::HashMap;
+#[cfg(not(feature = "no-git"))]
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+#[cfg(not(feature = "no-git"))]
+use std::sync::OnceLock;
+use std::sync::{Arc, Mutex};
+
+/// The ignore rules of a listed path, shared by the threads walking it.
+///
+/// The rules of each directory are read the first time one of its entries is checked. The
+/// `.ignore` files take precedence over the git rules, and deeper directories over their
+/// parents, like in `ripgrep`.
+pub struct IgnoreCache {
+    /// The listed path, as given and canonicalized.
+    start: PathBuf,
+    canonical_start: PathBuf,
+    /// The directory where the lookup of ignore files stops, the repository workdir if any.
+    root: PathBuf,
+    /// The `.ignore` files of each directory, and with `no-git` its `.gitignore` files too.
+    dirs: Mutex<HashMap<PathBuf, Option<Arc<Gitignore>>>>,
+    #[cfg(not(feature = "no-git"))]
+    repo: Option<Mutex<git2::Repository>>,
+    /// The files of the git index and their parent directories, read on the first ignored entry.
+    #[cfg(not(feature = "no-git"))]
+    tracked: OnceLock<TrackedPaths>,
+    /// The `.git/info/exclude` file and the global excludes of the repository.
+    #[cfg(feature = "no-git")]
+    excludes: Option<Gitignore>,
+}
+
+impl IgnoreCache {
+    fn with_root(path: &Path, root: Option<PathBuf>) -> Option<Self> {
+        let canonical_start = std::fs::canonicalize(path).ok()?;
+        Some(Self {
+            start: path.to_path_buf(),
+            root: root.unwrap_or_else(|| canonical_start.clone()),
+            canonical_start,
+            dirs: Mutex::new(HashMap::new()),
+            #[cfg(not(feature = "no-git"))]
+            repo: None,
+            #[cfg(not(feature = "no-git"))]
+            tracked: OnceLock::new(),
+            #[cfg(feature = "no-git")]
+            excludes: None,
+        })
+    }
+
+    /// Returns whether the entry at `path`, found while walking the listed path, is ignored.
+    pub fn is_ignored(&self, path: &Path, is_directory: bool) -> bool {
+        let path = match path.strip_prefix(&self.start) {
+            Ok(relative) => self.canonical_start.join(relative),
+            Err(_) => return false,
+        };
+        if !path.starts_with(&self.root) {
+            return false;
+        }
+
+        for dir in path.ancestors().skip(1) {
+            if let Some(matcher) = self.dir_matcher(dir) {
+                match matcher.matched_path_or_any_parents(&path, is_directory) {
+                    Match::Ignore(_) => return true,
+                    Match::Whitelist(_) => return false,
+                    Match::None => {}
+                }
+            }
+            if dir == self.root {
+                break;
+            }
+        }
+
+        self.is_ignored_by_repo(&path, is_directory)
+    }
+
+    fn dir_matcher(&self, dir: &Path) -> Option<Arc<Gitignore>> {
+        if let Some(matcher) = self.dirs.lock().unwrap().get(dir) {
+            return matcher.clone();
+        }
+
+        let mut builder = GitignoreBuilder::new(dir);
+        // with libgit2 the .gitignore files are handled by the repository
+        #[cfg(feature = "no-git")]
+        let files = [".gitignore", ".ignore"];
+        #[cfg(not(feature = "no-git"))]
+        let files = [".ignore"];
+        for file in files.iter().map(|file| dir.join(file)) {
+            if file.is_file() {
+                if let Some(err) = builder.add(&file) {
+                    crate::print_error!("{}: {}.", file.display(), err);
+                }
+            }
+        }
+        let matcher = builder
+            .build()
+            .ok()
+            .filter(|matcher| !matcher.is_empty())
+            .map(Arc::new);
+
+        self.dirs
+            .lock()
+            .unwrap()
+            .insert(dir.to_path_buf(), matcher.clone());
+        matcher
+    }
+}
+
+#[cfg(not(feature = "no-git"))]
+impl IgnoreCache {
+    pub fn new(path: &Path) -> Option<Self> {
+        let repo = git2::Repository::discover(path).ok();
+        let workdir = repo
+            .as_ref()
+            .and_then(|repo| repo.workdir())
+            .and_then(|workdir| std::fs::canonicalize(workdir).ok());
+
+        let mut cache = Self::with_root(path, workdir)?;
+        cache.repo = repo.map(Mutex::new);
+        Some(cache)
+    }
+
+    fn is_ignored_by_repo(&self, path: &Path, is_directory: bool) -> bool {
+        let Some(repo) = &self.repo else {
+            return false;
+        };
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return false;
+        };
+        if relative.as_os_str().is_empty() {
+            return false;
+        }
+
+        let tracked = {
+            let repo = repo.lock().unwrap();
+            if !repo.is_path_ignored(relative).unwrap_or(false) {
+                return false;
+            }
+            self.tracked.get_or_init(|| TrackedPaths::read(&repo))
+        };
+
+        // the ignore rules do not apply to files already tracked by git
+        if is_directory {
+            !tracked.dirs.contains(relative)
+        } else {
+            !tracked.files.contains(relative)
+        }
+    }
+}
+
+/// The paths of the git index, relative to the workdir.
+#[cfg(not(feature = "no-git"))]
+#[derive(Default)]
+struct TrackedPaths {
+    files: HashSet<PathBuf>,
+    /// Every directory with a tracked file somewhere below it.
+    dirs: HashSet<PathBuf>,
+}
+
+#[cfg(not(feature = "no-git"))]
+impl TrackedPaths {
+    fn read(repo: &git2::Repository) -> Self {
+        let mut tracked = Self::default();
+        let Ok(index) = repo.index() else {
+            return tracked;
+        };
+        for entry in index.iter() {
+            // git2-rs uses / separated paths even on Windows
+            let Ok(path) = std::str::from_utf8(&entry.path) else {
+                continue;
+            };
+            let path = PathBuf::from_iter(path.split('/'));
+            for dir in path.ancestors().skip(1) {
+                if dir.as_os_str().is_empty() || !tracked.dirs.insert(dir.to_path_buf()) {
+                    break;
+                }
+            }
+            tracked.files.insert(path);
+        }
+        tracked
+    }
+}
+
+#[cfg(feature = "no-git")]
+impl IgnoreCache {
+    pub fn new(path: &Path) -> Option<Self> {
+        let canonical = std::fs::canonicalize(path).ok()?;
+        let workdir = canonical
+            .ancestors()
+            .find(|dir| dir.join(".git").exists())
+            .map(Path::to_path_buf);
+
+        let excludes = workdir.as_ref().and_then(|workdir| {
+            let mut builder = GitignoreBuilder::new(workdir);
+            let global = ignore::gitignore::gitconfig_excludes_path();
+            let exclude = workdir.join(".git").join("info").join("exclude");
+            // the repository excludes take precedence over the global ones
+            for file in global.into_iter().chain([exclude]) {
+                if file.is_file() {
+                    builder.add(file);
+                }
+            }
+            builder.build().ok()
+        });
+
+        let mut cache = Self::with_root(path, workdir)?;
+        cache.excludes = excludes;
+        Some(cache)
+    }
+
+    fn is_ignored_by_repo(&self, path: &Path, is_directory: bool) -> bool {
+        match &self.excludes {
+            Some(excludes) => excludes
+                .matched_path_or_any_parents(path, is_directory)
+                .is_ignore(),
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IgnoreCache;
+    use assert_fs::prelude::*;
+
+    #[test]
+    fn test_dot_ignore_hierarchy() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child(".ignore").write_str("*.log\ntarget/\n").unwrap();
+        dir.child("sub/.ignore").write_str("!keep.log\n").unwrap();
+        dir.child("target").create_dir_all().unwrap();
+
+        let cache = IgnoreCache::new(dir.path()).unwrap();
+
+        assert!(cache.is_ignored(&dir.path().join("a.log"), false));
+        assert!(cache.is_ignored(&dir.path().join("target"), true));
+        assert!(!cache.is_ignored(&dir.path().join("target"), false));
+        assert!(cache.is_ignored(&dir.path().join("sub/other.log"), false));
+        assert!(!cache.is_ignored(&dir.path().join("sub/keep.log"), false));
+        assert!(!cache.is_ignored(&dir.path().join("main.rs"), false));
+    }
+
+    #[test]
+    fn test_gitignore_in_repository() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        #[cfg(not(feature = "no-git"))]
+        git2::Repository::init(dir.path()).unwrap();
+        dir.child(".gitignore").write_str("target/\n").unwrap();
+        dir.child("src/.gitignore").write_str("*.bak\n").unwrap();
+        dir.child(".git/info/exclude")
+            .write_str("local.txt\n")
+            .unwrap();
+        dir.child("target").create_dir_all().unwrap();
+        dir.child("src/old.bak").touch().unwrap();
+
+        let cache = IgnoreCache::new(&dir.path().join("src")).unwrap();
+
+        assert!(cache.is_ignored(&dir.path().join("src/old.bak"), false));
+        assert!(!cache.is_ignored(&dir.path().join("src/main.rs"), false));
+
+        let cache = IgnoreCache::new(dir.path()).unwrap();
+
+        assert!(cache.is_ignored(&dir.path().join("target"), true));
+        assert!(cache.is_ignored(&dir.path().join("local.txt"), false));
+        assert!(!cache.is_ignored(&dir.path().join("src"), true));
+    }
+
+    #[cfg(not(feature = "no-git"))]
+    #[test]
+    fn test_tracked_files_not_ignored() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        dir.child(".gitignore")
+            .write_str("*.bak\nbuild/\n")
+            .unwrap();
+        dir.child("tracked.bak").touch().unwrap();
+        dir.child("untracked.bak").touch().unwrap();
+        dir.child("build/keep/file.txt").touch().unwrap();
+        dir.child("build/tmp").create_dir_all().unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("tracked.bak")).unwrap();
+        index
+            .add_path(std::path::Path::new("build/keep/file.txt"))
+            .unwrap();
+        index.write().unwrap();
+
+        let cache = IgnoreCache::new(dir.path()).unwrap();
+
+        assert!(!cache.is_ignored(&dir.path().join("tracked.bak"), false));
+        assert!(cache.is_ignored(&dir.path().join("untracked.bak"), false));
+        assert!(!cache.is_ignored(&dir.path().join("build"), true));
+        assert!(!cache.is_ignored(&dir.path().join("build/keep"), true));
+        assert!(cache.is_ignored(&dir.path().join("build/tmp"), true));
+    }
+}
@@ -31,10 +31,12 @@ mod color;
 mod config_file;
 mod core;
 mod display;
+mod duplicates;
 mod flags;
 mod git;
 mod git_theme;
 mod icon;
+mod ignore_cache;
 mod meta;
 mod sort;
 mod theme;
@@ -1,11 +1,25 @@
+use super::OwnerCache;
+use super::acl::Acl;
+use super::capabilities::Capabilities;
 use crate::color::{ColoredString, Colors, Elem};
 use std::path::Path;
 
+/// The name of an extended attribute and the size of its value in bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtendedAttribute {
+    pub name: String,
+    pub size: usize,
+}
+
 #[derive(Clone, Debug)]
 pub struct AccessControl {
     has_acl: bool,
     selinux_context: String,
     smack_context: String,
+    xattrs: Vec<ExtendedAttribute>,
+    acl: Acl,
+    default_acl: Acl,
+    capabilities: Option<Capabilities>,
 }
 
 impl AccessControl {
@@ -16,18 +30,46 @@ impl AccessControl {
 
     #[cfg(unix)]
     pub fn for_path(path: &Path) -> Self {
-        let has_acl = !xattr::get(path, Method::Acl.name())
-            .unwrap_or_default()
-            .unwrap_or_default()
-            .is_empty();
-        let selinux_context = xattr::get(path, Method::Selinux.name())
-            .unwrap_or_default()
-            .unwrap_or_default();
-        let smack_context = xattr::get(path, Method::Smack.name())
-            .unwrap_or_default()
-            .unwrap_or_default();
+        let mut has_acl = false;
+        let mut selinux_context = Vec::new();
+        let mut smack_context = Vec::new();
+        let mut xattrs = Vec::new();
+        let mut acl = Acl::default();
+        let mut default_acl = Acl::default();
+        let mut capabilities = None;
+
+        // Listing the names first saves reading the attributes a file does not carry.
+        for name in xattr::list(path).into_iter().flatten() {
+            let value = xattr::get(path, &name)
+                .unwrap_or_default()
+                .unwrap_or_default();
+            match name.to_str() {
+                Some(name) if name == Method::Acl.name() => {
+                    has_acl = !value.is_empty();
+                    acl = Acl::parse(&value).unwrap_or_default();
+                }
+                Some(name) if name == Method::DefaultAcl.name() => {
+                    default_acl = Acl::parse(&value).unwrap_or_default();
+                }
+                Some(name) if name == Method::Selinux.name() => selinux_context = value.clone(),
+                Some(name) if name == Method::Smack.name() => smack_context = value.clone(),
+                Some(name) if name == Method::Capability.name() => {
+                    capabilities = Capabilities::parse(&value);
+                }
+                _ => {}
+            }
+            xattrs.push(ExtendedAttribute {
+                name: name.to_string_lossy().to_string(),
+                size: value.len(),
+            });
+        }
 
-        Self::from_data(has_acl, &selinux_context, &smack_context)
+        let mut access_control = Self::from_data(has_acl, &selinux_context, &smack_context);
+        access_control.xattrs = xattrs;
+        access_control.acl = acl;
+        access_control.default_acl = default_acl;
+        access_control.capabilities = capabilities;
+        access_control
     }
 
     fn from_data(has_acl: bool, selinux_context: &[u8], smack_context: &[u8]) -> Self {
@@ -37,9 +79,30 @@ impl AccessControl {
             has_acl,
             selinux_context,
             smack_context,
+            xattrs: Vec::new(),
+            acl: Acl::default(),
+            default_acl: Acl::default(),
+            capabilities: None,
         }
     }
 
+    /// The extended attributes of the file, in the order they are listed by the filesystem.
+    pub fn xattrs(&self) -> &[ExtendedAttribute] {
+        &self.xattrs
+    }
+
+    pub fn has_acl(&self) -> bool {
+        self.has_acl
+    }
+
+    pub fn selinux_context(&self) -> &str {
+        &self.selinux_context
+    }
+
+    pub fn smack_context(&self) -> &str {
+        &self.smack_context
+    }
+
     pub fn render_method(&self, colors: &Colors) -> ColoredString {
         if self.has_acl {
             colors.colorize('+', &Elem::Acl)
@@ -50,7 +113,66 @@ impl AccessControl {
         }
     }
 
-    pub fn render_context(&self, colors: &Colors) -> ColoredString {
+    pub fn render_xattrs_count(&self, colors: &Colors) -> ColoredString {
+        colors.colorize(self.xattrs.len().to_string(), &Elem::Context)
+    }
+
+    /// The lines listing the extended attributes under an entry, like `ls -@` on macOS.
+    pub fn render_xattrs(&self, colors: &Colors) -> String {
+        self.xattrs
+            .iter()
+            .map(|xattr| {
+                format!(
+                    "\t{}\t{:>6}\n",
+                    colors.colorize(&*xattr.name, &Elem::Context),
+                    xattr.size
+                )
+            })
+            .collect()
+    }
+
+    /// All the ACL entries like `getfacl` prints them, the default ones prefixed by `default:`.
+    pub fn acl_entries(&self, cache: &OwnerCache) -> Vec<String> {
+        let mut entries = self.acl.long_entries("", cache);
+        entries.extend(self.default_acl.long_entries("default:", cache));
+        entries
+    }
+
+    /// The named users and groups and the mask of the ACLs, in the short form of `setfacl`, if
+    /// there are any.
+    pub fn short_acl(&self, cache: &OwnerCache) -> Option<String> {
+        let mut entries = self.acl.short_entries("", cache);
+        entries.extend(self.default_acl.short_entries("d:", cache));
+        (!entries.is_empty()).then(|| entries.join(","))
+    }
+
+    pub fn render_acl(&self, colors: &Colors, cache: &OwnerCache) -> ColoredString {
+        match self.short_acl(cache) {
+            Some(acl) => colors.colorize(acl, &Elem::Acl),
+            None => colors.colorize('-', &Elem::NoAccess),
+        }
+    }
+
+    /// The lines listing the effective ACL entries under an entry, like `getfacl`.
+    pub fn render_acl_entries(&self, colors: &Colors, cache: &OwnerCache) -> String {
+        self.acl.render_entries("", colors, cache)
+            + &self.default_acl.render_entries("default:", colors, cache)
+    }
+
+    /// The file capabilities in the text form of `getcap`, if the file has any.
+    pub fn capabilities(&self) -> Option<String> {
+        self.capabilities.map(|capabilities| capabilities.to_text())
+    }
+
+    pub fn render_capabilities(&self, colors: &Colors) -> ColoredString {
+        match &self.capabilities {
+            Some(capabilities) => capabilities.render(colors),
+            None => colors.colorize('-', &Elem::NoAccess),
+        }
+    }
+
+    /// The SELinux and SMACK contexts joined by `+`, if there are any.
+    pub fn context(&self) -> Option<String> {
         let mut context = self.selinux_context.clone();
         if !self.smack_context.is_empty() {
             if !context.is_empty() {
@@ -58,18 +180,24 @@ impl AccessControl {
             }
             context += &self.smack_context;
         }
-        if context.is_empty() {
-            context += "?";
-        }
-        colors.colorize(context, &Elem::Context)
+        (!context.is_empty()).then_some(context)
+    }
+
+    pub fn render_context(&self, colors: &Colors) -> ColoredString {
+        colors.colorize(
+            self.context().unwrap_or_else(|| "?".to_string()),
+            &Elem::Context,
+        )
     }
 }
 
 #[cfg(unix)]
 enum Method {
     Acl,
+    DefaultAcl,
     Selinux,
     Smack,
+    Capability,
 }
 
 #[cfg(unix)]
@@ -77,16 +205,19 @@ impl Method {
     fn name(&self) -> &'static str {
         match self {
             Method::Acl => "system.posix_acl_access",
+            Method::DefaultAcl => "system.posix_acl_default",
             Method::Selinux => "security.selinux",
             Method::Smack => "security.SMACK64",
+            Method::Capability => "security.capability",
         }
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::AccessControl;
+    use super::{AccessControl, ExtendedAttribute};
     use crate::color::{Colors, ThemeOption};
+    use crate::meta::capabilities::Capabilities;
     use crossterm::style::{Color, Stylize};
 
     #[test]
@@ -140,6 +271,45 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_render_xattrs() {
+        let mut access_control = AccessControl::from_data(false, &[], &[]);
+        access_control.xattrs = vec![
+            ExtendedAttribute {
+                name: "user.origin".to_string(),
+                size: 42,
+            },
+            ExtendedAttribute {
+                name: "security.capability".to_string(),
+                size: 20,
+            },
+        ];
+        let colors = Colors::new(ThemeOption::NoColor);
+
+        assert_eq!("2", access_control.render_xattrs_count(&colors).content());
+        assert_eq!(
+            "\tuser.origin\t    42\n\tsecurity.capability\t    20\n",
+            access_control.render_xattrs(&colors)
+        );
+    }
+
+    #[test]
+    fn test_render_capabilities() {
+        let mut access_control = AccessControl::from_data(false, &[], &[]);
+        let colors = Colors::new(ThemeOption::NoColor);
+        assert_eq!("-", access_control.render_capabilities(&colors).content());
+
+        access_control.capabilities = Some(Capabilities {
+            permitted: 1 << 10,
+            inheritable: 0,
+            effective: true,
+        });
+        assert_eq!(
+            "cap_net_bind_service=ep",
+            access_control.render_capabilities(&colors).content()
+        );
+    }
+
     #[test]
     fn test_no_context() {
         let access_control = AccessControl::from_data(false, &[], &[]);
@@ -0,0 +1,283 @@
+//! This module parses the POSIX ACLs that Linux stores in the `system.posix_acl_access` and
+//! `system.posix_acl_default` extended attributes.
+
+use super::OwnerCache;
+use crate::color::{ColoredString, Colors, Elem};
+
+/// The version written in the header of every ACL attribute.
+const ACL_VERSION: u32 = 2;
+
+const READ: u16 = 4;
+const WRITE: u16 = 2;
+const EXECUTE: u16 = 1;
+
+/// Who an [AclEntry] applies to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AclTag {
+    /// The owner of the file, mirrored by the user permission bits.
+    UserObj,
+    User(u32),
+    /// The group of the file, mirrored by the group permission bits when there is no mask.
+    GroupObj,
+    Group(u32),
+    /// The upper bound of the permissions granted to named users and to groups.
+    Mask,
+    /// Everyone else, mirrored by the other permission bits.
+    Other,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AclEntry {
+    pub tag: AclTag,
+    /// The `rwx` bits, as in the permission bits of a file.
+    pub perm: u16,
+}
+
+/// The entries of an access or a default ACL, in the order they are stored.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Acl(pub Vec<AclEntry>);
+
+impl Acl {
+    /// Parse the value of an ACL extended attribute: a little-endian version header followed by
+    /// `(tag: u16, perm: u16, id: u32)` entries.
+    pub fn parse(value: &[u8]) -> Option<Self> {
+        let (header, entries) = value.split_first_chunk::<4>()?;
+        if u32::from_le_bytes(*header) != ACL_VERSION || entries.len() % 8 != 0 {
+            return None;
+        }
+
+        entries
+            .chunks_exact(8)
+            .map(|entry| {
+                let id = u32::from_le_bytes([entry[4], entry[5], entry[6], entry[7]]);
+                let tag = match u16::from_le_bytes([entry[0], entry[1]]) {
+                    0x01 => AclTag::UserObj,
+                    0x02 => AclTag::User(id),
+                    0x04 => AclTag::GroupObj,
+                    0x08 => AclTag::Group(id),
+                    0x10 => AclTag::Mask,
+                    0x20 => AclTag::Other,
+                    _ => return None,
+                };
+                let perm = u16::from_le_bytes([entry[2], entry[3]]) & (READ | WRITE | EXECUTE);
+                Some(AclEntry { tag, perm })
+            })
+            .collect::<Option<Vec<_>>>()
+            .map(Self)
+    }
+
+    /// The permissions actually granted by an entry, once restricted by the mask.
+    pub fn effective(&self, entry: &AclEntry) -> u16 {
+        let mask = self.0.iter().find(|entry| entry.tag == AclTag::Mask);
+        match (entry.tag, mask) {
+            (AclTag::User(_) | AclTag::GroupObj | AclTag::Group(_), Some(mask)) => {
+                entry.perm & mask.perm
+            }
+            _ => entry.perm,
+        }
+    }
+
+    /// The entries that the permission bits cannot express, i.e. the named users and groups and
+    /// the mask.
+    pub fn extended(&self) -> impl Iterator<Item = &AclEntry> {
+        self.0
+            .iter()
+            .filter(|entry| matches!(entry.tag, AclTag::User(_) | AclTag::Group(_) | AclTag::Mask))
+    }
+
+    /// The extended entries in the short form of `setfacl`, e.g. `u:alice:rw-,m::rwx`, each
+    /// preceded by `prefix`.
+    pub fn short_entries(&self, prefix: &str, cache: &OwnerCache) -> Vec<String> {
+        self.extended()
+            .map(|entry| {
+                let (kind, qualifier) = entry.tag.describe(cache);
+                format!("{prefix}{}:{qualifier}:{}", &kind[..1], rwx(entry.perm))
+            })
+            .collect()
+    }
+
+    /// The entries like `getfacl` prints them, e.g. `user:alice:rw-`, each preceded by `prefix`
+    /// and followed by the effective permissions when the mask restricts them.
+    pub fn long_entries(&self, prefix: &str, cache: &OwnerCache) -> Vec<String> {
+        self.0
+            .iter()
+            .map(|entry| {
+                let (kind, qualifier) = entry.tag.describe(cache);
+                let mut line = format!("{prefix}{kind}:{qualifier}:{}", rwx(entry.perm));
+                let effective = self.effective(entry);
+                if effective != entry.perm {
+                    line += &format!("\t#effective:{}", rwx(effective));
+                }
+                line
+            })
+            .collect()
+    }
+
+    /// The entries like [long_entries](Acl::long_entries), colored with the theme of the user,
+    /// group and permission blocks, one indented line each.
+    pub fn render_entries(&self, prefix: &str, colors: &Colors, cache: &OwnerCache) -> String {
+        self.0
+            .iter()
+            .map(|entry| {
+                let (kind, qualifier) = entry.tag.describe(cache);
+                let elem = match entry.tag {
+                    AclTag::UserObj | AclTag::User(_) => Elem::User,
+                    AclTag::GroupObj | AclTag::Group(_) => Elem::Group,
+                    AclTag::Mask | AclTag::Other => Elem::Acl,
+                };
+                let mut line = format!(
+                    "\t{}{}",
+                    colors.colorize(format!("{prefix}{kind}:{qualifier}:"), &elem),
+                    render_rwx(entry.perm, colors)
+                );
+                let effective = self.effective(entry);
+                if effective != entry.perm {
+                    line += &format!(
+                        "\t{}{}",
+                        colors.colorize("#effective:", &Elem::Acl),
+                        render_rwx(effective, colors)
+                    );
+                }
+                line + "\n"
+            })
+            .collect()
+    }
+}
+
+impl AclTag {
+    /// The kind of the entry and the name of the user or group it names, if any.
+    fn describe(&self, cache: &OwnerCache) -> (&'static str, String) {
+        match self {
+            AclTag::UserObj => ("user", String::new()),
+            AclTag::User(uid) => ("user", cache.user_name(*uid)),
+            AclTag::GroupObj => ("group", String::new()),
+            AclTag::Group(gid) => ("group", cache.group_name(*gid)),
+            AclTag::Mask => ("mask", String::new()),
+            AclTag::Other => ("other", String::new()),
+        }
+    }
+}
+
+fn bits(perm: u16) -> [(bool, char, Elem); 3] {
+    [
+        (perm & READ != 0, 'r', Elem::Read),
+        (perm & WRITE != 0, 'w', Elem::Write),
+        (perm & EXECUTE != 0, 'x', Elem::Exec),
+    ]
+}
+
+fn rwx(perm: u16) -> String {
+    bits(perm)
+        .into_iter()
+        .map(|(set, chr, _)| if set { chr } else { '-' })
+        .collect()
+}
+
+fn render_rwx(perm: u16, colors: &Colors) -> ColoredString {
+    let res = bits(perm)
+        .into_iter()
+        .map(|(set, chr, elem)| match set {
+            true => colors.colorize(chr, &elem).to_string(),
+            false => colors.colorize('-', &Elem::NoAccess).to_string(),
+        })
+        .collect::<String>();
+    ColoredString::new(Colors::default_style(), res)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Acl, AclEntry, AclTag};
+    use crate::color::{Colors, ThemeOption};
+    use crate::meta::OwnerCache;
+
+    /// Encode entries the way the kernel stores them in the extended attribute.
+    fn encode(entries: &[(u16, u16, u32)]) -> Vec<u8> {
+        let mut value = 2u32.to_le_bytes().to_vec();
+        for (tag, perm, id) in entries {
+            value.extend(tag.to_le_bytes());
+            value.extend(perm.to_le_bytes());
+            value.extend(id.to_le_bytes());
+        }
+        value
+    }
+
+    fn shared_dir_acl() -> Acl {
+        Acl::parse(&encode(&[
+            (0x01, 7, u32::MAX),
+            (0x02, 7, 4242),
+            (0x04, 5, u32::MAX),
+            (0x08, 6, 4343),
+            (0x10, 5, u32::MAX),
+            (0x20, 0, u32::MAX),
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let acl = shared_dir_acl();
+        assert_eq!(6, acl.0.len());
+        assert_eq!(
+            AclEntry {
+                tag: AclTag::User(4242),
+                perm: 7
+            },
+            acl.0[1]
+        );
+        assert_eq!(AclTag::Other, acl.0[5].tag);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert_eq!(None, Acl::parse(&[]));
+        assert_eq!(None, Acl::parse(&1u32.to_le_bytes()));
+        assert_eq!(None, Acl::parse(&encode(&[(0x40, 7, 0)])));
+        let mut truncated = encode(&[(0x01, 7, 0)]);
+        truncated.pop();
+        assert_eq!(None, Acl::parse(&truncated));
+    }
+
+    #[test]
+    fn test_effective() {
+        let acl = shared_dir_acl();
+        // the mask r-x removes the write bit of the named entries
+        assert_eq!(5, acl.effective(&acl.0[1]));
+        assert_eq!(4, acl.effective(&acl.0[3]));
+        // the owner and the others are not restricted by the mask
+        assert_eq!(7, acl.effective(&acl.0[0]));
+    }
+
+    #[test]
+    fn test_entries() {
+        let acl = shared_dir_acl();
+        let cache = OwnerCache::default();
+
+        assert_eq!(
+            vec!["u:4242:rwx", "g:4343:rw-", "m::r-x"],
+            acl.short_entries("", &cache)
+        );
+        assert_eq!(
+            vec![
+                "default:user::rwx",
+                "default:user:4242:rwx\t#effective:r-x",
+                "default:group::r-x",
+                "default:group:4343:rw-\t#effective:r--",
+                "default:mask::r-x",
+                "default:other::---",
+            ],
+            acl.long_entries("default:", &cache)
+        );
+    }
+
+    #[test]
+    fn test_render_entries() {
+        let acl = shared_dir_acl();
+        let colors = Colors::new(ThemeOption::NoColor);
+
+        assert_eq!(
+            "\tuser::rwx\n\tuser:4242:rwx\t#effective:r-x\n\tgroup::r-x\n\
+             \tgroup:4343:rw-\t#effective:r--\n\tmask::r-x\n\tother::---\n",
+            acl.render_entries("", &colors, &OwnerCache::default())
+        );
+    }
+}
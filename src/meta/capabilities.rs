@@ -0,0 +1,180 @@
+//! This module decodes the Linux file capabilities stored in the `security.capability`
+//! extended attribute.
+
+use crate::color::{ColoredString, Colors, Elem};
+
+const VFS_CAP_REVISION_MASK: u32 = 0xFF00_0000;
+const VFS_CAP_REVISION_1: u32 = 0x0100_0000;
+const VFS_CAP_REVISION_2: u32 = 0x0200_0000;
+const VFS_CAP_REVISION_3: u32 = 0x0300_0000;
+const VFS_CAP_FLAGS_EFFECTIVE: u32 = 0x0000_0001;
+
+/// The names of the capabilities, indexed by their number, as in `linux/capability.h`.
+const NAMES: [&str; 41] = [
+    "chown",
+    "dac_override",
+    "dac_read_search",
+    "fowner",
+    "fsetid",
+    "kill",
+    "setgid",
+    "setuid",
+    "setpcap",
+    "linux_immutable",
+    "net_bind_service",
+    "net_broadcast",
+    "net_admin",
+    "net_raw",
+    "ipc_lock",
+    "ipc_owner",
+    "sys_module",
+    "sys_rawio",
+    "sys_chroot",
+    "sys_ptrace",
+    "sys_pacct",
+    "sys_admin",
+    "sys_boot",
+    "sys_nice",
+    "sys_resource",
+    "sys_time",
+    "sys_tty_config",
+    "mknod",
+    "lease",
+    "audit_write",
+    "audit_control",
+    "setfcap",
+    "mac_override",
+    "mac_admin",
+    "syslog",
+    "wake_alarm",
+    "block_suspend",
+    "audit_read",
+    "perfmon",
+    "bpf",
+    "checkpoint_restore",
+];
+
+/// The capabilities granted to a program when it is executed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    pub permitted: u64,
+    pub inheritable: u64,
+    /// Whether the permitted capabilities are also raised in the effective set.
+    pub effective: bool,
+}
+
+impl Capabilities {
+    /// Parse the value of the `security.capability` attribute: a little-endian revision and
+    /// flags word followed by the permitted and inheritable sets, 32 bits at a time.
+    pub fn parse(value: &[u8]) -> Option<Self> {
+        let words: Vec<u32> = value
+            .chunks_exact(4)
+            .map(|word| u32::from_le_bytes([word[0], word[1], word[2], word[3]]))
+            .collect();
+        let magic = *words.first()?;
+        let sets = match magic & VFS_CAP_REVISION_MASK {
+            VFS_CAP_REVISION_1 => 1,
+            // the third revision only adds the id of the root user of the namespace
+            VFS_CAP_REVISION_2 | VFS_CAP_REVISION_3 => 2,
+            _ => return None,
+        };
+        let data = words.get(1..1 + 2 * sets)?;
+
+        let set = |offset: usize| {
+            (0..sets).fold(0u64, |set, index| {
+                set | (u64::from(data[2 * index + offset]) << (32 * index))
+            })
+        };
+        Some(Self {
+            permitted: set(0),
+            inheritable: set(1),
+            effective: magic & VFS_CAP_FLAGS_EFFECTIVE != 0,
+        })
+    }
+
+    /// The capabilities in the text form of `getcap`, e.g. `cap_net_admin,cap_net_raw=ep`.
+    ///
+    /// The capabilities sharing the same flags are grouped together.
+    pub fn to_text(self) -> String {
+        let mut groups: Vec<(String, Vec<String>)> = Vec::new();
+        for cap in 0..64 {
+            let bit = 1u64 << cap;
+            let mut flags = String::new();
+            if self.effective && self.permitted & bit != 0 {
+                flags.push('e');
+            }
+            if self.inheritable & bit != 0 {
+                flags.push('i');
+            }
+            if self.permitted & bit != 0 {
+                flags.push('p');
+            }
+            if flags.is_empty() {
+                continue;
+            }
+
+            let name = match NAMES.get(cap) {
+                Some(name) => format!("cap_{name}"),
+                None => format!("cap_{cap}"),
+            };
+            match groups.iter_mut().find(|(group, _)| *group == flags) {
+                Some((_, names)) => names.push(name),
+                None => groups.push((flags, vec![name])),
+            }
+        }
+
+        groups
+            .into_iter()
+            .map(|(flags, names)| format!("{}={flags}", names.join(",")))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    pub fn render(&self, colors: &Colors) -> ColoredString {
+        colors.colorize(self.to_text(), &Elem::Capabilities)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Capabilities;
+
+    fn encode(words: &[u32]) -> Vec<u8> {
+        words.iter().flat_map(|word| word.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn test_parse_revision_2() {
+        // cap_net_bind_service=ep, as set by `setcap cap_net_bind_service=ep`
+        let caps = Capabilities::parse(&encode(&[0x0200_0001, 1 << 10, 0, 0, 0])).unwrap();
+        assert_eq!(1 << 10, caps.permitted);
+        assert_eq!(0, caps.inheritable);
+        assert!(caps.effective);
+        assert_eq!("cap_net_bind_service=ep", caps.to_text());
+    }
+
+    #[test]
+    fn test_parse_revision_3_high_bits() {
+        // cap_bpf and cap_perfmon live in the second word
+        let caps = Capabilities::parse(&encode(&[0x0300_0000, 0, 0, 0b11 << 6, 0, 1000])).unwrap();
+        assert!(!caps.effective);
+        assert_eq!("cap_perfmon,cap_bpf=p", caps.to_text());
+    }
+
+    #[test]
+    fn test_groups_by_flags() {
+        let caps = Capabilities {
+            permitted: (1 << 12) | (1 << 13),
+            inheritable: 1 << 13,
+            effective: true,
+        };
+        assert_eq!("cap_net_admin=ep cap_net_raw=eip", caps.to_text());
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert_eq!(None, Capabilities::parse(&[]));
+        assert_eq!(None, Capabilities::parse(&encode(&[0x0400_0000, 0, 0])));
+        assert_eq!(None, Capabilities::parse(&encode(&[0x0200_0000, 0, 0])));
+    }
+}
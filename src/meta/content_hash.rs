@@ -0,0 +1,139 @@
+//! This module computes the hash of the content of regular files for the hash block.
+
+use super::FileType;
+use crate::color::{ColoredString, Colors, Elem};
+use crate::flags::HashFlag;
+use crate::flags::hash::HashAlgorithm;
+use sha2::Digest;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// The hexadecimal digest of the content of a file.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ContentHash(String);
+
+impl ContentHash {
+    /// Hash a regular file of `size` bytes, unless it is larger than the maximum size.
+    ///
+    /// Directories, symlinks and special files are not hashed, nor are the files that cannot
+    /// be read.
+    pub fn for_path(path: &Path, file_type: FileType, size: u64, flag: HashFlag) -> Option<Self> {
+        if !matches!(file_type, FileType::File { .. }) || size > flag.max_size {
+            return None;
+        }
+        let file = File::open(path).ok()?;
+        Self::from_reader(file, flag.algorithm).ok()
+    }
+
+    fn from_reader(mut reader: impl Read, algorithm: HashAlgorithm) -> io::Result<Self> {
+        let mut hasher = Hasher::new(algorithm);
+        let mut buffer = vec![0; 64 * 1024];
+        loop {
+            match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(read) => hasher.update(&buffer[..read]),
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(Self(hasher.finalize()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn render(&self, colors: &Colors) -> ColoredString {
+        colors.colorize(self.as_str(), &Elem::Hash)
+    }
+}
+
+enum Hasher {
+    Sha256(sha2::Sha256),
+    Blake3(Box<blake3::Hasher>),
+    Crc32(crc32fast::Hasher),
+}
+
+impl Hasher {
+    fn new(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::Sha256 => Self::Sha256(sha2::Sha256::new()),
+            HashAlgorithm::Blake3 => Self::Blake3(Box::default()),
+            HashAlgorithm::Crc32 => Self::Crc32(crc32fast::Hasher::new()),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Self::Sha256(hasher) => hasher.update(data),
+            Self::Blake3(hasher) => {
+                hasher.update(data);
+            }
+            Self::Crc32(hasher) => hasher.update(data),
+        }
+    }
+
+    fn finalize(self) -> String {
+        match self {
+            Self::Sha256(hasher) => hex(&hasher.finalize()),
+            Self::Blake3(hasher) => hasher.finalize().to_hex().to_string(),
+            Self::Crc32(hasher) => format!("{:08x}", hasher.finalize()),
+        }
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::ContentHash;
+    use crate::flags::HashFlag;
+    use crate::flags::hash::HashAlgorithm;
+    use crate::meta::FileType;
+    use std::fs;
+
+    #[test]
+    fn test_algorithms() {
+        let digest = |algorithm| {
+            ContentHash::from_reader(&b"abc"[..], algorithm)
+                .unwrap()
+                .as_str()
+                .to_string()
+        };
+
+        assert_eq!(
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            digest(HashAlgorithm::Sha256)
+        );
+        assert_eq!(
+            "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85",
+            digest(HashAlgorithm::Blake3)
+        );
+        assert_eq!("352441c2", digest(HashAlgorithm::Crc32));
+    }
+
+    #[test]
+    fn test_skips_large_and_special_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("file");
+        fs::write(&path, b"abc").unwrap();
+        let file = FileType::File {
+            uid: false,
+            exec: false,
+        };
+        let flag = HashFlag {
+            algorithm: HashAlgorithm::Crc32,
+            max_size: 3,
+        };
+
+        assert!(ContentHash::for_path(&path, file, 3, flag).is_some());
+        assert_eq!(None, ContentHash::for_path(&path, file, 4, flag));
+        assert_eq!(
+            None,
+            ContentHash::for_path(tmp.path(), FileType::Directory { uid: false }, 0, flag)
+        );
+    }
+}
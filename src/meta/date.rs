@@ -1,7 +1,7 @@
 use super::locale::current_locale;
 use crate::color::{ColoredString, Colors, Elem};
-use crate::flags::{DateFlag, Flags};
-use chrono::{DateTime, Duration, Local};
+use crate::flags::{DateFlag, Flags, TimeField};
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, TimeZone};
 use chrono_humanize::HumanTime;
 use std::fs::Metadata;
 use std::panic;
@@ -31,7 +31,106 @@ impl From<&Metadata> for Date {
     }
 }
 
+/// All the timestamps of a file, [Date::Invalid] for those the platform does not provide.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Timestamps {
+    pub modified: Date,
+    pub accessed: Date,
+    pub changed: Date,
+    pub created: Date,
+}
+
+impl From<&Metadata> for Timestamps {
+    fn from(meta: &Metadata) -> Self {
+        let date = |time: std::io::Result<SystemTime>| time.map_or(Date::Invalid, Date::from);
+
+        Self {
+            modified: Date::from(meta),
+            accessed: date(meta.accessed()),
+            changed: changed(meta),
+            created: date(meta.created()),
+        }
+    }
+}
+
+impl Timestamps {
+    /// The timestamp selected by the [TimeField].
+    pub fn get(&self, field: TimeField) -> &Date {
+        match field {
+            TimeField::Modified => &self.modified,
+            TimeField::Accessed => &self.accessed,
+            TimeField::Changed => &self.changed,
+            TimeField::Birth => &self.created,
+        }
+    }
+}
+
+#[cfg(unix)]
+fn changed(meta: &Metadata) -> Date {
+    use std::os::unix::fs::MetadataExt;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let nanos = Duration::from_nanos(meta.ctime_nsec().max(0) as u64);
+    let time = if meta.ctime() >= 0 {
+        UNIX_EPOCH.checked_add(Duration::from_secs(meta.ctime() as u64) + nanos)
+    } else {
+        UNIX_EPOCH.checked_sub(Duration::from_secs(meta.ctime().unsigned_abs()) - nanos)
+    };
+    time.map_or(Date::Invalid, Date::from)
+}
+
+// Windows has no status change time, only the time of the last write to the content.
+#[cfg(not(unix))]
+fn changed(_: &Metadata) -> Date {
+    Date::Invalid
+}
+
 impl Date {
+    /// The date as an RFC 3339 string, or [None] when it could not be read.
+    pub fn to_rfc3339(&self) -> Option<String> {
+        match self {
+            Self::Date(val) => Some(val.to_rfc3339()),
+            Self::Invalid => None,
+        }
+    }
+
+    /// Parse a point in time given either relatively to now, like `30m`, `2d` or `1w`, or as
+    /// an absolute local date like `2025-01-01` or `2025-01-01 12:30`, or an RFC 3339 date.
+    pub fn parse_time(value: &str) -> Option<DateTime<Local>> {
+        let value = value.trim();
+
+        if let Some(split) = value.find(|c: char| !c.is_ascii_digit()) {
+            let (number, unit) = value.split_at(split);
+            if let Ok(number) = number.parse::<i64>() {
+                let duration = match unit {
+                    "s" => Duration::try_seconds(number),
+                    "m" | "min" => Duration::try_minutes(number),
+                    "h" => Duration::try_hours(number),
+                    "d" => Duration::try_days(number),
+                    "w" => Duration::try_weeks(number),
+                    // same year length as for the iso date format
+                    "y" => number
+                        .checked_mul(31_556_952)
+                        .and_then(Duration::try_seconds),
+                    _ => None,
+                };
+                if let Some(duration) = duration {
+                    return Local::now().checked_sub_signed(duration);
+                }
+            }
+        }
+
+        if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+            return Some(date.with_timezone(&Local));
+        }
+        let naive = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .ok()
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .or_else(|| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").ok())
+            .or_else(|| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M").ok())?;
+        Local.from_local_datetime(&naive).earliest()
+    }
+
     pub fn render(&self, colors: &Colors, flags: &Flags) -> ColoredString {
         let date_string = self.date_string(flags);
         let elem = match self {
@@ -41,6 +140,18 @@ impl Date {
 
         colors.colorize(date_string, &elem)
     }
+
+    /// Render the date relatively to now, like `--date relative` does.
+    pub fn render_relative(&self, colors: &Colors) -> ColoredString {
+        match self {
+            Self::Date(val) => colors.colorize(
+                HumanTime::from(*val - Local::now()).to_string(),
+                &Elem::Date(val.timestamp()),
+            ),
+            Self::Invalid => colors.colorize('-', &Elem::InvalidDate),
+        }
+    }
+
     fn date_string(&self, flags: &Flags) -> String {
         let locale = current_locale();
 
@@ -69,9 +180,9 @@ impl Date {
 
 #[cfg(test)]
 mod test {
-    use super::Date;
+    use super::{Date, Timestamps};
     use crate::color::{Colors, ThemeOption};
-    use crate::flags::{DateFlag, Flags};
+    use crate::flags::{DateFlag, Flags, TimeField};
     use crate::meta::locale::current_locale;
     use chrono::{DateTime, Duration, Local};
     use crossterm::style::{Color, Stylize};
@@ -345,6 +456,58 @@ mod test {
         fs::remove_file(file_path).unwrap();
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_timestamps() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("file");
+        fs::File::create(&path).unwrap();
+        let accessed = Local::now() - Duration::days(3);
+        let modified = Local::now() - Duration::days(2);
+        fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_times(
+                fs::FileTimes::new()
+                    .set_accessed(accessed.into())
+                    .set_modified(modified.into()),
+            )
+            .unwrap();
+
+        let timestamps = Timestamps::from(&path.metadata().unwrap());
+        assert_eq!(&Date::Date(modified), timestamps.get(TimeField::Modified));
+        assert_eq!(&Date::Date(accessed), timestamps.get(TimeField::Accessed));
+        // setting the times is itself a status change
+        assert!(timestamps.get(TimeField::Changed) > timestamps.get(TimeField::Modified));
+    }
+
+    #[test]
+    fn test_parse_time() {
+        let now = Local::now();
+        let two_days = Date::parse_time("2d").unwrap();
+        assert!((now - Duration::days(2) - two_days).num_seconds().abs() < 5);
+        let ninety_minutes = Date::parse_time("90min").unwrap();
+        assert!(
+            (now - Duration::minutes(90) - ninety_minutes)
+                .num_seconds()
+                .abs()
+                < 5
+        );
+
+        let new_year = Date::parse_time("2025-01-01").unwrap();
+        assert_eq!("2025-01-01 00:00:00", new_year.format("%F %T").to_string());
+        let noon = Date::parse_time("2025-01-01 12:30").unwrap();
+        assert_eq!("2025-01-01 12:30:00", noon.format("%F %T").to_string());
+        assert_eq!(
+            DateTime::parse_from_rfc3339("2025-01-01T12:00:00+00:00").unwrap(),
+            Date::parse_time("2025-01-01T12:00:00Z").unwrap()
+        );
+
+        assert_eq!(None, Date::parse_time("2x"));
+        assert_eq!(None, Date::parse_time("yesterday"));
+    }
+
     #[test]
     #[cfg(all(not(windows), target_arch = "x86_64"))]
     fn test_bad_date() {
@@ -0,0 +1,111 @@
+//! This module counts the entries of directories, for the entries block and `--dir-count`.
+
+use super::FileType;
+use crate::color::{ColoredString, Colors, Elem};
+use crate::flags::{Display, Flags, SizeFlag};
+use std::ffi::OsString;
+use std::path::Path;
+
+/// The number of entries of a directory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Entries(u64);
+
+impl Entries {
+    /// Count the entries of a directory by reading it, leaving out the hidden entries unless they
+    /// are displayed.
+    ///
+    /// The count is the same whatever the layout: the entries left out by the filters, the ignore
+    /// files or the predicates are still counted, even when the listed content was read. Other
+    /// file types and the directories that cannot be read have no count.
+    pub fn for_dir(path: &Path, file_type: FileType, display: Display) -> Option<Self> {
+        if !matches!(file_type, FileType::Directory { .. }) {
+            return None;
+        }
+
+        let names = path
+            .read_dir()
+            .ok()?
+            .filter_map(Result::ok)
+            .map(|entry| entry.file_name());
+        Some(Self::from_names(names, display))
+    }
+
+    /// Count the entries already read from a directory, before they were filtered, like
+    /// [for_dir](Entries::for_dir) does.
+    pub fn from_names(names: impl Iterator<Item = OsString>, display: Display) -> Self {
+        let show_hidden = matches!(
+            display,
+            Display::All | Display::AlmostAll | Display::SystemProtected
+        );
+        let count = names
+            .filter(|name| show_hidden || !name.to_string_lossy().starts_with('.'))
+            .count();
+        Self(count as u64)
+    }
+
+    pub fn count(&self) -> u64 {
+        self.0
+    }
+
+    pub fn render(&self, colors: &Colors) -> ColoredString {
+        colors.colorize(self.0.to_string(), &Elem::Entries)
+    }
+
+    /// Render the count in the size block, its value aligned like the sizes to `val_alignment`.
+    pub fn render_as_size(
+        &self,
+        colors: &Colors,
+        flags: &Flags,
+        val_alignment: Option<usize>,
+    ) -> ColoredString {
+        let value = self.0.to_string();
+        let left_pad = " ".repeat(
+            val_alignment
+                .unwrap_or_default()
+                .saturating_sub(value.len()),
+        );
+        let unit = match (flags.size, self.0) {
+            (SizeFlag::Default, 1) => " entry",
+            (SizeFlag::Default, _) => " entries",
+            _ => "e",
+        };
+        ColoredString::new(
+            Colors::default_style(),
+            format!(
+                "{left_pad}{}",
+                colors.colorize(format!("{value}{unit}"), &Elem::Entries)
+            ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Entries;
+    use crate::flags::Display;
+    use crate::meta::FileType;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_count_entries() {
+        let tmp = tempdir().unwrap();
+        for name in ["a", "b", ".hidden"] {
+            fs::write(tmp.path().join(name), "").unwrap();
+        }
+        let dir = FileType::Directory { uid: false };
+
+        let count = |display| Entries::for_dir(tmp.path(), dir, display).unwrap();
+        assert_eq!(2, count(Display::VisibleOnly).count());
+        assert_eq!(3, count(Display::AlmostAll).count());
+
+        let file = FileType::File {
+            uid: false,
+            exec: false,
+        };
+        assert_eq!(
+            None,
+            Entries::for_dir(&tmp.path().join("a"), file, Display::All)
+        );
+    }
+}
@@ -82,6 +82,20 @@ impl FileType {
         }
     }
 
+    /// A stable, lowercase name of the file type, used by the machine readable outputs.
+    pub fn name(self) -> &'static str {
+        match self {
+            FileType::BlockDevice => "block-device",
+            FileType::CharDevice => "char-device",
+            FileType::Directory { .. } => "directory",
+            FileType::File { .. } => "file",
+            FileType::SymLink { .. } => "symlink",
+            FileType::Pipe => "pipe",
+            FileType::Socket => "socket",
+            FileType::Special => "special",
+        }
+    }
+
     pub fn is_dirlike(self) -> bool {
         matches!(
             self,
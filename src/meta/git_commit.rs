@@ -0,0 +1,96 @@
+//! This module defines the last commit touching a file, for the git-commit, git-author and
+//! git-date blocks.
+
+use super::Date;
+use crate::color::{ColoredString, Colors, Elem};
+
+/// How many hex digits of the commit id are shown, like `git log --oneline`.
+const SHORT_ID_LEN: usize = 7;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GitCommit {
+    id: String,
+    author: String,
+    date: Date,
+}
+
+impl GitCommit {
+    #[cfg(not(feature = "no-git"))]
+    pub fn new(commit: &git2::Commit) -> Self {
+        use chrono::{Local, TimeZone};
+
+        let author = commit.author();
+        let date = Local
+            .timestamp_opt(author.when().seconds(), 0)
+            .single()
+            .map_or(Date::Invalid, Date::Date);
+
+        Self {
+            id: commit.id().to_string(),
+            author: String::from_utf8_lossy(author.name_bytes()).into_owned(),
+            date,
+        }
+    }
+
+    /// The full hex id of the commit.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn short_id(&self) -> &str {
+        &self.id[..SHORT_ID_LEN.min(self.id.len())]
+    }
+
+    pub fn author(&self) -> &str {
+        &self.author
+    }
+
+    pub fn date(&self) -> &Date {
+        &self.date
+    }
+
+    pub fn render_id(&self, colors: &Colors) -> ColoredString {
+        colors.colorize(self.short_id().to_string(), &Elem::GitCommit)
+    }
+
+    pub fn render_author(&self, colors: &Colors) -> ColoredString {
+        colors.colorize(self.author.clone(), &Elem::GitAuthor)
+    }
+
+    /// The date of the commit relatively to now, like `3 days ago`, whatever the date flag.
+    pub fn render_date(&self, colors: &Colors) -> ColoredString {
+        self.date.render_relative(colors)
+    }
+}
+
+#[cfg(not(feature = "no-git"))]
+#[cfg(test)]
+mod test {
+    use super::GitCommit;
+    use crate::meta::Date;
+    use git2::{Repository, Signature, Time};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_from_commit() {
+        let tmp = tempdir().unwrap();
+        let repo = Repository::init(tmp.path()).unwrap();
+        let sig =
+            Signature::new("Jane Doe", "jane@example.com", &Time::new(1_700_000_000, 0)).unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let id = repo
+            .commit(Some("HEAD"), &sig, &sig, "initial commit", &tree, &[])
+            .unwrap();
+
+        let commit = GitCommit::new(&repo.find_commit(id).unwrap());
+        assert_eq!(id.to_string(), commit.id());
+        assert_eq!(&id.to_string()[..7], commit.short_id());
+        assert_eq!("Jane Doe", commit.author());
+        assert!(
+            matches!(commit.date(), Date::Date(date) if date.timestamp() == 1_700_000_000),
+            "{:?}",
+            commit.date()
+        );
+    }
+}
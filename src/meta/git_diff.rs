@@ -0,0 +1,61 @@
+//! This module defines the lines added and removed in a file since HEAD, for the git-diff block.
+
+use crate::color::{ColoredString, Colors, Elem};
+use serde::Serialize;
+use std::fmt;
+
+/// The line counts of `git diff HEAD --stat`, summed over the content of the directories.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct GitDiffStat {
+    added: usize,
+    removed: usize,
+}
+
+impl GitDiffStat {
+    #[cfg_attr(feature = "no-git", allow(dead_code))]
+    pub fn new(added: usize, removed: usize) -> Self {
+        Self { added, removed }
+    }
+
+    /// Render the counts like `+12 -3`.
+    pub fn render(&self, colors: &Colors) -> ColoredString {
+        ColoredString::new(
+            Colors::default_style(),
+            format!(
+                "{} {}",
+                colors.colorize(format!("+{}", self.added), &Elem::GitAdded),
+                colors.colorize(format!("-{}", self.removed), &Elem::GitRemoved)
+            ),
+        )
+    }
+}
+
+impl fmt::Display for GitDiffStat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "+{} -{}", self.added, self.removed)
+    }
+}
+
+impl std::ops::AddAssign for GitDiffStat {
+    fn add_assign(&mut self, other: Self) {
+        self.added += other.added;
+        self.removed += other.removed;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::GitDiffStat;
+    use crate::color::{Colors, ThemeOption};
+
+    #[test]
+    fn test_render() {
+        let mut stat = GitDiffStat::new(12, 3);
+        stat += GitDiffStat::new(1, 0);
+        assert_eq!("+13 -3", stat.to_string());
+        assert_eq!(
+            "+13 -3",
+            stat.render(&Colors::new(ThemeOption::NoColor)).to_string()
+        );
+    }
+}
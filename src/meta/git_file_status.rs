@@ -1,8 +1,9 @@
 use crate::color::{self, ColoredString, Colors};
 use crate::git::GitStatus;
 use crate::git_theme::GitTheme;
+use serde::Serialize;
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
 pub struct GitFileStatus {
     pub index: GitStatus,
     pub workdir: GitStatus,
@@ -43,6 +44,37 @@ impl GitFileStatus {
         }
     }
 
+    /// The status of a submodule, from its entry in the index of the superproject and from the
+    /// state of its checkout.
+    #[cfg(not(feature = "no-git"))]
+    pub fn for_submodule(status: git2::SubmoduleStatus) -> Self {
+        use git2::SubmoduleStatus as S;
+
+        let index = match status {
+            s if s.contains(S::INDEX_ADDED) => GitStatus::NewInIndex,
+            s if s.contains(S::INDEX_DELETED) => GitStatus::Deleted,
+            s if s.contains(S::INDEX_MODIFIED) => GitStatus::Modified,
+            _ => GitStatus::Unmodified,
+        };
+        let workdir = match status {
+            s if s.contains(S::WD_UNINITIALIZED) => GitStatus::Uninitialized,
+            s if s.contains(S::WD_ADDED) => GitStatus::NewInWorkdir,
+            s if s.contains(S::WD_DELETED) => GitStatus::Deleted,
+            s if s.contains(S::WD_MODIFIED) => GitStatus::NewCommits,
+            s if s.intersects(S::WD_INDEX_MODIFIED | S::WD_WD_MODIFIED | S::WD_UNTRACKED) => {
+                GitStatus::Modified
+            }
+            _ => GitStatus::Unmodified,
+        };
+
+        // like the files, a clean submodule has no status
+        if index == GitStatus::Unmodified && workdir == GitStatus::Unmodified {
+            Self::default()
+        } else {
+            Self { index, workdir }
+        }
+    }
+
     pub fn render(&self, colors: &Colors, git_theme: &GitTheme) -> ColoredString {
         let res = [
             colors.colorize(
@@ -0,0 +1,513 @@
+//! This module lists the trees of a Git revision for `--git-rev`, building the [Meta] of their
+//! entries from the objects of the repository rather than from the disk, so that nothing is
+//! checked out.
+
+use super::Meta;
+use crate::flags::Flags;
+use std::path::Path;
+#[cfg(all(unix, not(feature = "no-git")))]
+use {super::Permissions, crate::flags::PermissionFlag};
+#[cfg(not(feature = "no-git"))]
+use {
+    super::{
+        Date, FileType, GitCommit, Indicator, Name, PermissionsOrAttributes, Size, SymLink,
+        Timestamps,
+    },
+    crate::flags::{Display, Layout},
+    crate::git::GitCache,
+    std::collections::HashSet,
+    std::path::{Component, PathBuf},
+};
+
+/// The revision listing the index rather than a tree of the history.
+#[cfg(not(feature = "no-git"))]
+const INDEX_REV: &str = ":";
+
+/// The modes of the tree entries, which Git limits to these.
+#[cfg(not(feature = "no-git"))]
+mod modes {
+    pub const TREE: i32 = 0o040000;
+    pub const BLOB: i32 = 0o100644;
+    pub const EXECUTABLE: i32 = 0o100755;
+    pub const LINK: i32 = 0o120000;
+    pub const COMMIT: i32 = 0o160000;
+}
+
+/// A revision of a repository, or its index, whose trees are listed.
+pub struct GitRev {
+    #[cfg(not(feature = "no-git"))]
+    repo: git2::Repository,
+    /// The canonicalized workdir, the listed paths being looked up in the trees from there.
+    #[cfg(not(feature = "no-git"))]
+    workdir: PathBuf,
+    #[cfg(not(feature = "no-git"))]
+    tree: git2::Oid,
+    /// The commit of the revision, or HEAD for the index, to walk the history from.
+    #[cfg(not(feature = "no-git"))]
+    commit: Option<git2::Oid>,
+    /// The date of the commit of the revision, given to all its entries.
+    #[cfg(not(feature = "no-git"))]
+    date: Date,
+    /// The index when it is listed, its entries being dated by the modification time of the
+    /// files when they were staged.
+    #[cfg(not(feature = "no-git"))]
+    index: Option<git2::Index>,
+}
+
+#[cfg(feature = "no-git")]
+impl GitRev {
+    pub fn open(_path: &Path, _rev: &str) -> Result<Self, String> {
+        Err("lsd is built without Git support".to_string())
+    }
+
+    pub fn meta(&self, _path: &Path, _depth: usize, _flags: &Flags) -> Result<Meta, String> {
+        Err("lsd is built without Git support".to_string())
+    }
+}
+
+#[cfg(not(feature = "no-git"))]
+impl GitRev {
+    /// Open the repository holding `path` at the revision `rev`.
+    pub fn open(path: &Path, rev: &str) -> Result<Self, String> {
+        Self::try_open(path, rev).map_err(|err| err.message().to_string())
+    }
+
+    fn try_open(path: &Path, rev: &str) -> Result<Self, git2::Error> {
+        // the path may only exist in the revision
+        let existing = path
+            .ancestors()
+            .find(|ancestor| !ancestor.as_os_str().is_empty() && ancestor.exists())
+            .unwrap_or(Path::new("."));
+        let repo = git2::Repository::discover(existing)?;
+        let workdir = repo
+            .workdir()
+            .and_then(|workdir| std::fs::canonicalize(workdir).ok())
+            .ok_or_else(|| git2::Error::from_str("cannot list a bare repository"))?;
+
+        let (tree, commit, index) = if rev == INDEX_REV {
+            // the trees of the index are written in memory, not to the object database
+            repo.odb()?.add_new_mempack_backend(1000)?;
+            let mut index = repo.index()?;
+            let tree = index.write_tree()?;
+            let head = repo.head().and_then(|head| head.peel_to_commit()).ok();
+            (tree, head.map(|head| head.id()), Some(index))
+        } else {
+            let object = repo.revparse_single(rev)?;
+            let tree = object.peel_to_tree()?.id();
+            let commit = object.peel_to_commit().ok().map(|commit| commit.id());
+            (tree, commit, None)
+        };
+
+        let date = match (&index, commit) {
+            (None, Some(commit)) => GitCommit::new(&repo.find_commit(commit)?).date().clone(),
+            _ => Date::Invalid,
+        };
+
+        Ok(Self {
+            repo,
+            workdir,
+            tree,
+            commit,
+            date,
+            index,
+        })
+    }
+
+    /// Build the [Meta] of `path` as it is in the revision, with its content down to `depth`
+    /// like [Meta::recurse_into].
+    pub fn meta(&self, path: &Path, depth: usize, flags: &Flags) -> Result<Meta, String> {
+        self.try_meta(path, depth, flags)
+            .map_err(|err| err.message().to_string())
+    }
+
+    fn try_meta(&self, path: &Path, depth: usize, flags: &Flags) -> Result<Meta, git2::Error> {
+        let rel_path = self
+            .relative_path(path)
+            .ok_or_else(|| git2::Error::from_str("not in the Git repository"))?;
+        let (mode, id) = if rel_path.as_os_str().is_empty() {
+            (modes::TREE, self.tree)
+        } else {
+            let entry = self
+                .repo
+                .find_tree(self.tree)?
+                .get_path(&rel_path)
+                .map_err(|_| git2::Error::from_str("no such file or directory in the revision"))?;
+            (entry.filemode(), entry.id())
+        };
+
+        let mut meta = self.entry_meta(path, &rel_path, mode, id, flags)?;
+        meta.content = self.content(path, &rel_path, mode, id, depth, flags)?;
+        if let Some(rel_dir) = rel_path
+            .parent()
+            .filter(|_| flags.blocks.displays_git_commit())
+        {
+            self.read_last_commits(rel_dir, std::slice::from_mut(&mut meta));
+        }
+        Ok(meta)
+    }
+
+    /// The path relative to the workdir, also for the paths that only exist in the revision.
+    fn relative_path(&self, path: &Path) -> Option<PathBuf> {
+        let absolute = std::fs::canonicalize(path).ok().or_else(|| {
+            let parent = path
+                .parent()
+                .filter(|parent| !parent.as_os_str().is_empty());
+            let parent = std::fs::canonicalize(parent.unwrap_or(Path::new("."))).ok()?;
+            Some(parent.join(path.file_name()?))
+        })?;
+        absolute
+            .strip_prefix(&self.workdir)
+            .ok()
+            .map(Path::to_path_buf)
+    }
+
+    /// The entries of the tree `id`, filtered like the ones read by [Meta::recurse_into].
+    fn content(
+        &self,
+        dir: &Path,
+        rel_dir: &Path,
+        mode: i32,
+        id: git2::Oid,
+        depth: usize,
+        flags: &Flags,
+    ) -> Result<Option<Vec<Meta>>, git2::Error> {
+        if depth == 0 || mode != modes::TREE {
+            return Ok(None);
+        }
+        if flags.display == Display::DirectoryOnly && flags.layout != Layout::Tree {
+            return Ok(None);
+        }
+
+        let mut content = Vec::new();
+        for entry in self.repo.find_tree(id)?.iter() {
+            // the names that are not valid UTF-8 cannot be matched against the globs
+            let Some(name) = entry.name() else {
+                continue;
+            };
+            if flags.ignore_globs.0.is_match(name)
+                || (flags.display == Display::VisibleOnly && name.starts_with('.'))
+                || (flags.layout == Layout::Tree
+                    && flags.display == Display::DirectoryOnly
+                    && entry.filemode() != modes::TREE)
+            {
+                continue;
+            }
+
+            let path = dir.join(name);
+            let rel_path = rel_dir.join(name);
+            let mut meta =
+                self.entry_meta(&path, &rel_path, entry.filemode(), entry.id(), flags)?;
+            meta.content = self.content(
+                &path,
+                &rel_path,
+                entry.filemode(),
+                entry.id(),
+                depth - 1,
+                flags,
+            )?;
+            if meta.matches_filters(flags) || meta.has_listed_content() {
+                content.push(meta);
+            }
+        }
+
+        if flags.blocks.displays_git_commit() {
+            self.read_last_commits(rel_dir, &mut content);
+        }
+        Ok(Some(content))
+    }
+
+    fn entry_meta(
+        &self,
+        path: &Path,
+        rel_path: &Path,
+        mode: i32,
+        id: git2::Oid,
+        flags: &Flags,
+    ) -> Result<Meta, git2::Error> {
+        let mut symlink = SymLink::new(None, false);
+        let file_type = match mode {
+            modes::TREE | modes::COMMIT => FileType::Directory { uid: false },
+            modes::LINK => {
+                let blob = self.repo.find_blob(id)?;
+                let target = String::from_utf8_lossy(blob.content()).into_owned();
+                let target_mode = self.link_target(rel_path, &target);
+                symlink = SymLink::new(Some(target), target_mode.is_some());
+                FileType::SymLink {
+                    is_dir: target_mode == Some(modes::TREE),
+                }
+            }
+            _ => FileType::File {
+                uid: false,
+                exec: mode == modes::EXECUTABLE,
+            },
+        };
+
+        let bytes = match mode {
+            modes::TREE if flags.total_size.0 => self.tree_size(id)?,
+            modes::TREE | modes::COMMIT => 0,
+            _ => self.repo.odb()?.read_header(id)?.0 as u64,
+        };
+
+        let date = match &self.index {
+            // the root is not an entry of the index
+            Some(_) if rel_path.as_os_str().is_empty() => Date::Invalid,
+            Some(index) => index
+                .get_path(rel_path, 0)
+                .and_then(|entry| {
+                    use chrono::{Local, TimeZone};
+                    Local
+                        .timestamp_opt(i64::from(entry.mtime.seconds()), 0)
+                        .single()
+                })
+                .map_or(Date::Invalid, Date::Date),
+            None => self.date.clone(),
+        };
+
+        let mut name = Name::new(path, file_type);
+        name.set_submodule(mode == modes::COMMIT);
+
+        Ok(Meta {
+            name,
+            path: path.to_path_buf(),
+            permissions_or_attributes: Self::permissions(mode, flags),
+            dates: Some(Timestamps {
+                modified: date.clone(),
+                accessed: Date::Invalid,
+                changed: date,
+                created: Date::Invalid,
+            }),
+            owner: None,
+            file_type,
+            size: Some(Size::with_allocated(bytes, bytes)),
+            symlink,
+            indicator: Indicator::from(file_type),
+            inode: None,
+            links: None,
+            content: None,
+            access_control: None,
+            git_status: None,
+            git_commit: None,
+            git_diff: None,
+            repo_summary: None,
+            inode_flags: None,
+            content_hash: None,
+            entries: None,
+        })
+    }
+
+    /// The permissions a checkout gives to the entries of the mode.
+    #[cfg(unix)]
+    fn permissions(mode: i32, flags: &Flags) -> Option<PermissionsOrAttributes> {
+        let bits = match mode {
+            modes::TREE | modes::COMMIT | modes::EXECUTABLE => 0o755,
+            modes::LINK => 0o777,
+            _ => 0o644,
+        };
+        match flags.permission {
+            PermissionFlag::Disable => None,
+            _ => Some(PermissionsOrAttributes::Permissions(
+                Permissions::from_mode(bits),
+            )),
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn permissions(_mode: i32, _flags: &Flags) -> Option<PermissionsOrAttributes> {
+        None
+    }
+
+    /// The mode of the entry a link points to in the revision. Absolute targets are looked up
+    /// on the disk.
+    fn link_target(&self, rel_path: &Path, target: &str) -> Option<i32> {
+        let target = Path::new(target);
+        if target.is_absolute() {
+            return match target.metadata() {
+                Ok(metadata) if metadata.is_dir() => Some(modes::TREE),
+                Ok(_) => Some(modes::BLOB),
+                Err(_) => None,
+            };
+        }
+
+        let mut resolved = PathBuf::new();
+        for component in rel_path.parent()?.join(target).components() {
+            match component {
+                // out of the repository
+                Component::ParentDir if !resolved.pop() => return None,
+                Component::Normal(name) => resolved.push(name),
+                _ => {}
+            }
+        }
+        if resolved.as_os_str().is_empty() {
+            return Some(modes::TREE);
+        }
+        let tree = self.repo.find_tree(self.tree).ok()?;
+        tree.get_path(&resolved).ok().map(|entry| entry.filemode())
+    }
+
+    /// The sum of the sizes of the blobs under the tree `id`, for `--total-size`.
+    fn tree_size(&self, id: git2::Oid) -> Result<u64, git2::Error> {
+        let odb = self.repo.odb()?;
+        let mut total = 0;
+        self.repo
+            .find_tree(id)?
+            .walk(git2::TreeWalkMode::PreOrder, |_, entry| {
+                if entry.kind() == Some(git2::ObjectType::Blob) {
+                    total += odb
+                        .read_header(entry.id())
+                        .map_or(0, |(size, _)| size as u64);
+                }
+                git2::TreeWalkResult::Ok
+            })?;
+        Ok(total)
+    }
+
+    /// Set the last commit from the revision touching each of the `metas` of the directory
+    /// `rel_dir`, like the git-commit block does for the files on the disk.
+    fn read_last_commits(&self, rel_dir: &Path, metas: &mut [Meta]) {
+        let Some(commit) = self.commit.and_then(|id| self.repo.find_commit(id).ok()) else {
+            return;
+        };
+        let names: HashSet<String> = metas.iter().map(|meta| meta.name.name.clone()).collect();
+        let Ok(mut commits) = GitCache::walk_last_commits(&self.repo, &commit, rel_dir, names)
+        else {
+            return;
+        };
+        for meta in metas {
+            meta.git_commit = commits.remove(&meta.name.name);
+        }
+    }
+}
+
+#[cfg(not(feature = "no-git"))]
+#[cfg(test)]
+mod test {
+    use super::GitRev;
+    use crate::flags::Flags;
+    use crate::meta::{FileType, Meta};
+    use git2::{Repository, Signature};
+    use std::fs;
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    fn commit(repo: &Repository, msg: &str) {
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.update_all(["*"], None).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = Signature::now("name", "email").unwrap();
+        let parent = repo.head().and_then(|head| head.peel_to_commit()).ok();
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, msg, &tree, &parents)
+            .unwrap();
+    }
+
+    fn names(meta: &Meta) -> Vec<&str> {
+        let mut names: Vec<&str> = meta
+            .content
+            .iter()
+            .flatten()
+            .map(|meta| meta.name.name.as_str())
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    fn find<'a>(meta: &'a Meta, name: &str) -> &'a Meta {
+        meta.content
+            .iter()
+            .flatten()
+            .find(|meta| meta.name.name == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_list_revision() {
+        let tmp = tempdir().unwrap();
+        let repo = Repository::init(tmp.path()).unwrap();
+        fs::create_dir(tmp.path().join("dir")).unwrap();
+        fs::write(tmp.path().join("dir/file"), "12345").unwrap();
+        fs::write(tmp.path().join("removed"), "1").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{PermissionsExt, symlink};
+            let permissions = fs::Permissions::from_mode(0o755);
+            fs::set_permissions(tmp.path().join("removed"), permissions).unwrap();
+            symlink("dir", tmp.path().join("link")).unwrap();
+        }
+        commit(&repo, "first");
+        fs::remove_file(tmp.path().join("removed")).unwrap();
+        fs::write(tmp.path().join("dir/file"), "1").unwrap();
+        commit(&repo, "second");
+        fs::write(tmp.path().join("untracked"), "").unwrap();
+
+        let flags = Flags::default();
+        let rev = GitRev::open(tmp.path(), "HEAD~1").unwrap();
+        let root = rev.meta(tmp.path(), 2, &flags).unwrap();
+        #[cfg(unix)]
+        assert_eq!(vec!["dir", "link", "removed"], names(&root));
+        #[cfg(not(unix))]
+        assert_eq!(vec!["dir", "removed"], names(&root));
+
+        let dir = find(&root, "dir");
+        assert_eq!(FileType::Directory { uid: false }, dir.file_type);
+        assert_eq!(vec!["file"], names(dir));
+        let file = find(dir, "file");
+        assert_eq!(Some(5), file.size.map(|size| size.get_bytes()));
+        assert_eq!(tmp.path().join("dir/file"), file.path);
+
+        #[cfg(unix)]
+        {
+            let removed = find(&root, "removed");
+            assert_eq!(
+                FileType::File {
+                    uid: false,
+                    exec: true
+                },
+                removed.file_type
+            );
+            let link = find(&root, "link");
+            assert_eq!(FileType::SymLink { is_dir: true }, link.file_type);
+            assert_eq!(Some("dir".to_string()), link.symlink.symlink_string());
+        }
+
+        // a file that is only in the revision
+        let removed = rev.meta(&tmp.path().join("removed"), 1, &flags).unwrap();
+        assert_eq!(Some(1), removed.size.map(|size| size.get_bytes()));
+        assert!(rev.meta(&tmp.path().join("untracked"), 1, &flags).is_err());
+
+        assert!(GitRev::open(tmp.path(), "no-such-rev").is_err());
+    }
+
+    #[test]
+    fn test_list_index() {
+        let tmp = tempdir().unwrap();
+        let repo = Repository::init(tmp.path()).unwrap();
+        fs::write(tmp.path().join("committed"), "1").unwrap();
+        commit(&repo, "first");
+        fs::write(tmp.path().join("staged"), "12").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("staged")).unwrap();
+        index.write().unwrap();
+        fs::write(tmp.path().join("untracked"), "").unwrap();
+        let objects = || {
+            fs::read_dir(tmp.path().join(".git/objects"))
+                .unwrap()
+                .count()
+        };
+        let objects_before = objects();
+
+        let flags = Flags::default();
+        let rev = GitRev::open(tmp.path(), ":").unwrap();
+        let root = rev.meta(tmp.path(), 1, &flags).unwrap();
+        assert_eq!(vec!["committed", "staged"], names(&root));
+        assert_eq!(
+            Some(2),
+            find(&root, "staged").size.map(|size| size.get_bytes())
+        );
+        // the trees of the index are not written to the repository
+        assert_eq!(objects_before, objects());
+    }
+}
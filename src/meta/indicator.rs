@@ -3,7 +3,7 @@ use crate::flags::Flags;
 use crate::meta::FileType;
 
 #[derive(Clone, Debug)]
-pub struct Indicator(&'static str);
+pub struct Indicator(&'static str, bool);
 
 impl From<FileType> for Indicator {
     fn from(file_type: FileType) -> Self {
@@ -16,13 +16,20 @@ impl From<FileType> for Indicator {
             _ => "",
         };
 
-        Indicator(res)
+        Indicator(res, false)
     }
 }
 
 impl Indicator {
+    /// The indicator of a directory on which another filesystem is mounted.
+    pub fn mount_point() -> Self {
+        Indicator("/", true)
+    }
+
     pub fn render(&self, flags: &Flags) -> ColoredString {
-        if flags.display_indicators.0 {
+        if self.1 && flags.mountpoint_indicator.0 {
+            ColoredString::new(Colors::default_style(), "+".into())
+        } else if flags.display_indicators.0 {
             ColoredString::new(Colors::default_style(), self.0.to_string())
         } else {
             ColoredString::new(Colors::default_style(), "".into())
@@ -33,7 +40,7 @@ impl Indicator {
 #[cfg(test)]
 mod test {
     use super::Indicator;
-    use crate::flags::{Flags, Indicators};
+    use crate::flags::{Flags, Indicators, MountpointIndicator};
     use crate::meta::FileType;
 
     #[test]
@@ -48,6 +55,22 @@ mod test {
         assert_eq!("/", file_type.render(&flags).to_string());
     }
 
+    #[test]
+    fn test_mount_point_indicator() {
+        let mut flags = Flags {
+            display_indicators: Indicators(true),
+            ..Default::default()
+        };
+
+        let indicator = Indicator::mount_point();
+
+        assert_eq!("/", indicator.render(&flags).to_string());
+
+        flags.display_indicators = Indicators(false);
+        flags.mountpoint_indicator = MountpointIndicator(true);
+        assert_eq!("+", indicator.render(&flags).to_string());
+    }
+
     #[test]
     fn test_executable_file_indicator() {
         let flags = Flags {
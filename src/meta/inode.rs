@@ -4,6 +4,7 @@ use std::fs::Metadata;
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub struct INode {
     index: Option<u64>,
+    device: Option<u64>,
 }
 
 impl From<&Metadata> for INode {
@@ -12,17 +13,33 @@ impl From<&Metadata> for INode {
         use std::os::unix::fs::MetadataExt;
 
         let index = meta.ino();
+        let device = meta.dev();
 
-        Self { index: Some(index) }
+        Self {
+            index: Some(index),
+            device: Some(device),
+        }
     }
 
     #[cfg(windows)]
     fn from(_: &Metadata) -> Self {
-        Self { index: None }
+        Self {
+            index: None,
+            device: None,
+        }
     }
 }
 
 impl INode {
+    pub fn index(&self) -> Option<u64> {
+        self.index
+    }
+
+    /// The id of the device holding the file, which changes when crossing a mountpoint.
+    pub fn device(&self) -> Option<u64> {
+        self.device
+    }
+
     pub fn render(&self, colors: &Colors) -> ColoredString {
         match self.index {
             Some(i) => colors.colorize(i.to_string(), &Elem::INode { valid: true }),
@@ -0,0 +1,138 @@
+//! This module reads the inode flags set by `chattr`, like immutable or append-only.
+
+use super::FileType;
+use crate::color::{ColoredString, Colors, Elem};
+use std::path::Path;
+
+/// The flags shown by `lsattr`, in its order, without the ones describing how the filesystem
+/// stores the file, like extents or indexed directories.
+const FLAGS: [(u32, char); 20] = [
+    (0x0000_0001, 's'), // secure deletion
+    (0x0000_0002, 'u'), // undeletable
+    (0x0000_0008, 'S'), // synchronous updates
+    (0x0001_0000, 'D'), // synchronous directory updates
+    (0x0000_0010, 'i'), // immutable
+    (0x0000_0020, 'a'), // append only
+    (0x0000_0040, 'd'), // no dump
+    (0x0000_0080, 'A'), // no atime updates
+    (0x0000_0004, 'c'), // compressed
+    (0x0000_0800, 'E'), // encrypted
+    (0x0000_4000, 'j'), // data journaling
+    (0x0000_8000, 't'), // no tail merging
+    (0x0002_0000, 'T'), // top of directory hierarchy
+    (0x0080_0000, 'C'), // no copy on write
+    (0x0200_0000, 'x'), // direct access
+    (0x4000_0000, 'F'), // casefold
+    (0x1000_0000, 'N'), // inline data
+    (0x2000_0000, 'P'), // project hierarchy
+    (0x0010_0000, 'V'), // verity
+    (0x0000_0400, 'm'), // no compression
+];
+
+const IMMUTABLE: u32 = 0x0000_0010;
+const APPEND: u32 = 0x0000_0020;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct InodeFlags(pub u32);
+
+impl InodeFlags {
+    /// Read the flags of a regular file or a directory with the `FS_IOC_GETFLAGS` ioctl, which
+    /// fails on filesystems not supporting it.
+    #[cfg(target_os = "linux")]
+    pub fn for_path(path: &Path, file_type: FileType) -> Option<Self> {
+        use std::os::unix::fs::OpenOptionsExt;
+        use std::os::unix::io::AsRawFd;
+
+        // like lsattr, do not open devices, pipes or sockets, which could block or have effects
+        if !matches!(
+            file_type,
+            FileType::File { .. } | FileType::Directory { .. }
+        ) {
+            return None;
+        }
+
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .custom_flags(libc::O_NONBLOCK | libc::O_NOFOLLOW)
+            .open(path)
+            .ok()?;
+        // the kernel writes an int, whatever the long in the signature of the ioctl says
+        let mut flags: libc::c_int = 0;
+        let res = unsafe { libc::ioctl(file.as_raw_fd(), libc::FS_IOC_GETFLAGS, &mut flags) };
+        if res == 0 {
+            Some(Self(flags as u32))
+        } else {
+            None
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn for_path(_: &Path, _: FileType) -> Option<Self> {
+        None
+    }
+
+    /// The letters of the set flags, as `chattr` names them.
+    pub fn letters(&self) -> String {
+        FLAGS
+            .iter()
+            .filter(|(flag, _)| self.0 & flag != 0)
+            .map(|(_, letter)| letter)
+            .collect()
+    }
+
+    pub fn render(&self, colors: &Colors) -> ColoredString {
+        let letters = self.letters();
+        if letters.is_empty() {
+            return colors.colorize('-', &Elem::NoAccess);
+        }
+
+        let res = FLAGS
+            .iter()
+            .filter(|(flag, _)| self.0 & flag != 0)
+            .map(|(flag, letter)| {
+                let elem = match *flag {
+                    IMMUTABLE | APPEND => Elem::Immutable,
+                    _ => Elem::InodeFlag,
+                };
+                colors.colorize(*letter, &elem).to_string()
+            })
+            .collect::<String>();
+        ColoredString::new(Colors::default_style(), res)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::InodeFlags;
+    use crate::color::{Colors, ThemeOption};
+
+    #[test]
+    fn test_letters() {
+        // immutable, no atime updates and extents, which is not shown
+        let flags = InodeFlags(0x10 | 0x80 | 0x8_0000);
+        assert_eq!("iA", flags.letters());
+        assert_eq!("", InodeFlags(0).letters());
+    }
+
+    #[test]
+    fn test_render() {
+        let colors = Colors::new(ThemeOption::NoColor);
+        assert_eq!("-", InodeFlags(0x8_0000).render(&colors).content());
+        assert_eq!("ac", InodeFlags(0x24).render(&colors).content());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_for_path() {
+        use crate::meta::FileType;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let fifo = tmp.path().join("fifo");
+        std::process::Command::new("mkfifo")
+            .arg(&fifo)
+            .status()
+            .unwrap();
+
+        assert_eq!(None, InodeFlags::for_path(&fifo, FileType::Pipe));
+    }
+}
@@ -0,0 +1,245 @@
+//! This module guesses the MIME type of regular files from their first bytes, for the files whose
+//! name says nothing of their content.
+
+use super::FileType;
+use crate::color::{ColoredString, Colors};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// How many bytes are read to guess the type, enough for the `ustar` magic of tar archives.
+const SNIFF_LEN: usize = 512;
+
+/// The magic bytes at the start of a file, with the type and the usual extension they give.
+const MAGIC: &[(&[u8], &str, Option<&str>)] = &[
+    (b"\x7fELF", "application/x-executable", Some("elf")),
+    (b"\x89PNG\r\n\x1a\n", "image/png", Some("png")),
+    (b"\xff\xd8\xff", "image/jpeg", Some("jpg")),
+    (b"GIF87a", "image/gif", Some("gif")),
+    (b"GIF89a", "image/gif", Some("gif")),
+    (b"II*\0", "image/tiff", Some("tiff")),
+    (b"MM\0*", "image/tiff", Some("tiff")),
+    (b"%PDF-", "application/pdf", Some("pdf")),
+    (b"\x1f\x8b", "application/gzip", Some("gz")),
+    (b"BZh", "application/x-bzip2", Some("bz2")),
+    (b"\xfd7zXZ\0", "application/x-xz", Some("xz")),
+    (b"\x28\xb5\x2f\xfd", "application/zstd", Some("zst")),
+    (b"PK\x03\x04", "application/zip", Some("zip")),
+    (
+        b"7z\xbc\xaf\x27\x1c",
+        "application/x-7z-compressed",
+        Some("7z"),
+    ),
+    (b"SQLite format 3\0", "application/vnd.sqlite3", Some("db")),
+    (b"\0asm", "application/wasm", Some("wasm")),
+    (b"\xcf\xfa\xed\xfe", "application/x-mach-binary", None),
+    (
+        b"MZ",
+        "application/vnd.microsoft.portable-executable",
+        Some("exe"),
+    ),
+    (b"OggS", "audio/ogg", Some("ogg")),
+    (b"fLaC", "audio/flac", Some("flac")),
+    (b"ID3", "audio/mpeg", Some("mp3")),
+];
+
+/// The interpreters of the scripts, by the name of their command without its version.
+const INTERPRETERS: &[(&str, &str, &str)] = &[
+    ("sh", "text/x-shellscript", "sh"),
+    ("bash", "text/x-shellscript", "sh"),
+    ("dash", "text/x-shellscript", "sh"),
+    ("ksh", "text/x-shellscript", "sh"),
+    ("zsh", "text/x-shellscript", "sh"),
+    ("fish", "text/x-shellscript", "fish"),
+    ("python", "text/x-python", "py"),
+    ("perl", "text/x-perl", "pl"),
+    ("ruby", "text/x-ruby", "rb"),
+    ("node", "text/javascript", "js"),
+    ("nodejs", "text/javascript", "js"),
+    ("deno", "text/javascript", "js"),
+    ("lua", "text/x-lua", "lua"),
+    ("php", "text/x-php", "php"),
+];
+
+/// The MIME type guessed for a file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Mime {
+    essence: &'static str,
+    extension: Option<&'static str>,
+}
+
+impl Mime {
+    /// Read the start of a regular file to guess its type. Other file types and the files that
+    /// cannot be read have none.
+    pub fn sniff(path: &Path, file_type: FileType) -> Option<Self> {
+        if !matches!(file_type, FileType::File { .. }) {
+            return None;
+        }
+
+        let mut head = Vec::with_capacity(SNIFF_LEN);
+        File::open(path)
+            .ok()?
+            .take(SNIFF_LEN as u64)
+            .read_to_end(&mut head)
+            .ok()?;
+        Some(Self::from_bytes(&head))
+    }
+
+    fn from_bytes(head: &[u8]) -> Self {
+        let mime = |essence, extension| Self { essence, extension };
+
+        if head.is_empty() {
+            return mime("inode/x-empty", None);
+        }
+        if let Some(line) = head.strip_prefix(b"#!") {
+            return Self::from_shebang(line);
+        }
+        if let Some(&(_, essence, extension)) =
+            MAGIC.iter().find(|(magic, _, _)| head.starts_with(magic))
+        {
+            return mime(essence, extension);
+        }
+        if head.starts_with(b"RIFF") && head.len() >= 12 {
+            match &head[8..12] {
+                b"WEBP" => return mime("image/webp", Some("webp")),
+                b"WAVE" => return mime("audio/wav", Some("wav")),
+                b"AVI " => return mime("video/x-msvideo", Some("avi")),
+                _ => {}
+            }
+        }
+        if head.get(4..8) == Some(b"ftyp") {
+            return mime("video/mp4", Some("mp4"));
+        }
+        if head.get(257..262) == Some(b"ustar") {
+            return mime("application/x-tar", Some("tar"));
+        }
+
+        // the head may end in the middle of a character
+        let text = match std::str::from_utf8(head) {
+            Ok(_) => true,
+            Err(err) => err.error_len().is_none(),
+        };
+        if text && !head.contains(&0) {
+            mime("text/plain", Some("txt"))
+        } else {
+            mime("application/octet-stream", None)
+        }
+    }
+
+    /// The type of a script from the first line after its `#!`, like `/usr/bin/env python3`.
+    fn from_shebang(line: &[u8]) -> Self {
+        let line = String::from_utf8_lossy(line);
+        let mut words = line.lines().next().unwrap_or_default().split_whitespace();
+        let mut command = words
+            .next()
+            .map(|program| program.rsplit('/').next().unwrap_or(program));
+        if command == Some("env") {
+            command = words.find(|word| !word.starts_with('-'));
+        }
+        let command = command
+            .unwrap_or_default()
+            .trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+
+        match INTERPRETERS.iter().find(|(name, _, _)| *name == command) {
+            Some(&(_, essence, extension)) => Self {
+                essence,
+                extension: Some(extension),
+            },
+            None => Self {
+                essence: "text/x-script",
+                extension: None,
+            },
+        }
+    }
+
+    /// The type, like `image/png`.
+    pub fn essence(&self) -> &'static str {
+        self.essence
+    }
+
+    /// The extension that files of this type usually have, to find their icon.
+    pub fn extension(&self) -> Option<&'static str> {
+        self.extension
+    }
+
+    /// The top-level type, like `image`.
+    pub fn category(&self) -> &'static str {
+        self.essence.split('/').next().unwrap_or(self.essence)
+    }
+
+    pub fn render(&self, colors: &Colors) -> ColoredString {
+        colors.colorize_mime(self.essence.to_string(), self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Mime;
+    use crate::meta::FileType;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn essence(head: &[u8]) -> &'static str {
+        Mime::from_bytes(head).essence()
+    }
+
+    #[test]
+    fn test_magic_bytes() {
+        assert_eq!("application/x-executable", essence(b"\x7fELF\x02\x01\x01"));
+        assert_eq!("image/png", essence(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"));
+        assert_eq!("application/gzip", essence(b"\x1f\x8b\x08\0"));
+        assert_eq!("application/pdf", essence(b"%PDF-1.7\n"));
+        assert_eq!("image/webp", essence(b"RIFF\0\0\0\0WEBPVP8 "));
+        assert_eq!("video/mp4", essence(b"\0\0\0\x20ftypisom"));
+
+        let mut tar = vec![0; 512];
+        tar[257..263].copy_from_slice(b"ustar\0");
+        tar[0] = b'a';
+        assert_eq!("application/x-tar", essence(&tar));
+    }
+
+    #[test]
+    fn test_text_and_binary() {
+        assert_eq!("inode/x-empty", essence(b""));
+        assert_eq!("text/plain", essence("héllo\n".as_bytes()));
+        // cut in the middle of the 'é'
+        assert_eq!("text/plain", essence(&"hé".as_bytes()[..2]));
+        assert_eq!("application/octet-stream", essence(b"\x01\x00\x02\xff"));
+    }
+
+    #[test]
+    fn test_shebang() {
+        let script = |line: &str| Mime::from_bytes(line.as_bytes());
+
+        assert_eq!("text/x-shellscript", script("#!/bin/sh\necho").essence());
+        assert_eq!(Some("sh"), script("#! /bin/bash -e\n").extension());
+        assert_eq!(
+            "text/x-python",
+            script("#!/usr/bin/env python3\n").essence()
+        );
+        assert_eq!("text/x-python", script("#!/usr/bin/python3.12").essence());
+        assert_eq!(
+            "text/x-perl",
+            script("#!/usr/bin/env -S perl -w\n").essence()
+        );
+        assert_eq!("text/x-script", script("#!/usr/bin/awk -f\n").essence());
+        assert_eq!("text", script("#!/usr/bin/awk -f\n").category());
+    }
+
+    #[test]
+    fn test_sniff() {
+        let tmp = tempdir().unwrap();
+        let path = tmp.path().join("run");
+        fs::write(&path, "#!/usr/bin/env ruby\nputs 1\n").unwrap();
+        let file = FileType::File {
+            uid: false,
+            exec: true,
+        };
+
+        assert_eq!(Some("rb"), Mime::sniff(&path, file).unwrap().extension());
+        assert_eq!(
+            None,
+            Mime::sniff(tmp.path(), FileType::Directory { uid: false })
+        );
+    }
+}
@@ -1,16 +1,27 @@
 mod access_control;
+mod acl;
+mod capabilities;
+mod content_hash;
 mod date;
+mod entries;
 mod filetype;
+mod git_commit;
+mod git_diff;
 pub mod git_file_status;
+mod git_rev;
 mod indicator;
 mod inode;
+mod inode_flags;
 mod links;
 mod locale;
+mod mime;
 pub mod name;
 pub mod owner;
 mod permissions;
 mod permissions_or_attributes;
+mod repo_summary;
 mod size;
+mod size_accumulator;
 mod symlink;
 
 #[cfg(windows)]
@@ -19,23 +30,37 @@ mod windows_attributes;
 mod windows_utils;
 
 pub use self::access_control::AccessControl;
-pub use self::date::Date;
+pub use self::content_hash::ContentHash;
+pub use self::date::{Date, Timestamps};
+pub use self::entries::Entries;
 pub use self::filetype::FileType;
+pub use self::git_commit::GitCommit;
+pub use self::git_diff::GitDiffStat;
 pub use self::git_file_status::GitFileStatus;
+pub use self::git_rev::GitRev;
 pub use self::indicator::Indicator;
 pub use self::inode::INode;
+pub use self::inode_flags::InodeFlags;
 pub use self::links::Links;
+pub use self::mime::Mime;
 pub use self::name::Name;
 pub use self::owner::{Cache as OwnerCache, Owner};
 pub use self::permissions::Permissions;
 use self::permissions_or_attributes::PermissionsOrAttributes;
+pub use self::repo_summary::RepoSummary;
 pub use self::size::Size;
+pub use self::size_accumulator::SizeAccumulator;
+use self::size_accumulator::{DirSizes, TreeSize, hard_link_key};
 pub use self::symlink::SymLink;
 
-use crate::flags::{Display, Flags, Layout, PermissionFlag};
+use crate::flags::blocks::Block;
+use crate::flags::{Display, Flags, Layout, PermissionFlag, TimeField};
 use crate::{ExitCode, print_error};
 
-use crate::git::GitCache;
+use crate::git::{GitCache, GitStatus};
+use crate::ignore_cache::IgnoreCache;
+use rayon::prelude::*;
+use std::fs::{DirEntry, Metadata};
 use std::io::{self, Error, ErrorKind};
 use std::path::{Component, Path, PathBuf};
 
@@ -46,7 +71,7 @@ pub struct Meta {
     pub name: Name,
     pub path: PathBuf,
     pub permissions_or_attributes: Option<PermissionsOrAttributes>,
-    pub date: Option<Date>,
+    pub dates: Option<Timestamps>,
     pub owner: Option<Owner>,
     pub file_type: FileType,
     pub size: Option<Size>,
@@ -57,14 +82,22 @@ pub struct Meta {
     pub content: Option<Vec<Meta>>,
     pub access_control: Option<AccessControl>,
     pub git_status: Option<GitFileStatus>,
+    pub git_commit: Option<GitCommit>,
+    pub git_diff: Option<GitDiffStat>,
+    /// The summary of the repository, for the listed directories only.
+    pub repo_summary: Option<RepoSummary>,
+    pub inode_flags: Option<InodeFlags>,
+    pub content_hash: Option<ContentHash>,
+    pub entries: Option<Entries>,
 }
 
 impl Meta {
     pub fn recurse_into(
-        &self,
+        &mut self,
         depth: usize,
         flags: &Flags,
         cache: Option<&GitCache>,
+        ignore: Option<&IgnoreCache>,
     ) -> io::Result<(Option<Vec<Meta>>, ExitCode)> {
         if depth == 0 {
             return Ok((None, ExitCode::OK));
@@ -85,13 +118,23 @@ impl Meta {
         }
 
         let entries = match self.path.read_dir() {
-            Ok(entries) => entries,
+            Ok(entries) => entries.collect::<io::Result<Vec<DirEntry>>>()?,
             Err(err) => {
                 print_error!("{}: {}.", self.path.display(), err);
                 return Ok((None, ExitCode::MinorIssue));
             }
         };
 
+        // the entries were just read, they are counted here rather than reading them again
+        if (flags.blocks.0.contains(&Block::Entries) || flags.dir_count.0)
+            && matches!(self.file_type, FileType::Directory { .. })
+        {
+            self.entries = Some(Entries::from_names(
+                entries.iter().map(DirEntry::file_name),
+                flags.display,
+            ));
+        }
+
         let mut content: Vec<Meta> = Vec::new();
 
         if matches!(flags.display, Display::All | Display::SystemProtected)
@@ -107,155 +150,508 @@ impl Meta {
             )?;
             "..".clone_into(&mut parent_meta.name.name);
 
-            current_meta.git_status = cache.and_then(|cache| cache.get(&current_meta.path, true));
-            parent_meta.git_status = cache.and_then(|cache| cache.get(&parent_meta.path, true));
+            current_meta.set_git_status(
+                cache.and_then(|cache| cache.get(&current_meta.path, true)),
+                flags,
+            );
+            parent_meta.set_git_status(
+                cache.and_then(|cache| cache.get(&parent_meta.path, true)),
+                flags,
+            );
+            current_meta.read_block_data(flags);
+            parent_meta.read_block_data(flags);
 
             content.push(current_meta);
             content.push(parent_meta);
         }
 
+        // submodules and nested repositories have statuses of their own
+        let nested_cache = cache.and_then(|cache| cache.nested(&self.path));
+        let cache = nested_cache.as_ref().or(cache);
+        // the entries are looked up by their canonicalized path, built from the one of the
+        // directory rather than canonicalizing each of them
+        let git_dir = cache.and_then(|_| std::fs::canonicalize(&self.path).ok());
+        let git = cache.zip(git_dir.as_deref());
+
+        // The entries are processed in directory order whether they are walked sequentially or
+        // by the thread pool, the results are collected back in that same order.
+        let entry_metas: Vec<io::Result<(Option<Meta>, ExitCode)>> = if flags.threads.is_parallel()
+        {
+            entries
+                .par_iter()
+                .map(|entry| Self::entry_meta(entry, self.device(), depth, flags, git, ignore))
+                .collect()
+        } else {
+            entries
+                .iter()
+                .map(|entry| Self::entry_meta(entry, self.device(), depth, flags, git, ignore))
+                .collect()
+        };
+
         let mut exit_code = ExitCode::OK;
 
-        for entry in entries {
-            let entry = entry?;
-            let path = entry.path();
+        for entry_meta in entry_metas {
+            let (entry_meta, entry_exit_code) = entry_meta?;
+            exit_code.set_if_greater(entry_exit_code);
+            content.extend(entry_meta);
+        }
+
+        if let Some(cache) = cache.filter(|_| flags.blocks.displays_git_commit()) {
+            cache.read_last_commits(&self.path, &mut content);
+        }
+
+        Ok((Some(content), exit_code))
+    }
+
+    /// Build the [Meta] of a single directory entry, recursing into it if needed.
+    ///
+    /// The returned [Meta] is [None] when the entry is filtered out or cannot be read.
+    fn entry_meta(
+        entry: &DirEntry,
+        parent_device: Option<u64>,
+        depth: usize,
+        flags: &Flags,
+        git: Option<(&GitCache, &Path)>,
+        ignore: Option<&IgnoreCache>,
+    ) -> io::Result<(Option<Meta>, ExitCode)> {
+        let path = entry.path();
+        let cache = git.map(|(cache, _)| cache);
+
+        let name = path
+            .file_name()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "invalid file name"))?;
 
-            let name = path
-                .file_name()
-                .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "invalid file name"))?;
+        if flags.ignore_globs.0.is_match(name) {
+            return Ok((None, ExitCode::OK));
+        }
 
-            if flags.ignore_globs.0.is_match(name) {
-                continue;
+        #[cfg(windows)]
+        let is_hidden =
+            name.to_string_lossy().starts_with('.') || windows_utils::is_path_hidden(&path);
+        #[cfg(not(windows))]
+        let is_hidden = name.to_string_lossy().starts_with('.');
+
+        #[cfg(windows)]
+        let is_system = windows_utils::is_path_system(&path);
+        #[cfg(not(windows))]
+        let is_system = false;
+
+        match flags.display {
+            // show hidden files, but ignore system protected files
+            Display::All | Display::AlmostAll if is_system => return Ok((None, ExitCode::OK)),
+            // ignore hidden and system protected files
+            Display::VisibleOnly if is_hidden || is_system => return Ok((None, ExitCode::OK)),
+            _ => {}
+        }
+
+        if let Some(ignore) = ignore {
+            if ignore.is_ignored(&path, entry.file_type()?.is_dir()) {
+                return Ok((None, ExitCode::OK));
             }
+        }
+
+        let git_path = git.map(|(cache, dir)| (cache, dir.join(name)));
+
+        // checked before recursing, not to walk the ignored build directories
+        if flags.git_hide_ignored.0
+            && git_path
+                .as_ref()
+                .is_some_and(|(cache, path)| cache.is_ignored(path))
+        {
+            return Ok((None, ExitCode::OK));
+        }
 
-            #[cfg(windows)]
-            let is_hidden =
-                name.to_string_lossy().starts_with('.') || windows_utils::is_path_hidden(&path);
-            #[cfg(not(windows))]
-            let is_hidden = name.to_string_lossy().starts_with('.');
-
-            #[cfg(windows)]
-            let is_system = windows_utils::is_path_system(&path);
-            #[cfg(not(windows))]
-            let is_system = false;
-
-            match flags.display {
-                // show hidden files, but ignore system protected files
-                Display::All | Display::AlmostAll if is_system => continue,
-                // ignore hidden and system protected files
-                Display::VisibleOnly if is_hidden || is_system => continue,
-                _ => {}
+        let mut entry_meta = match Self::from_path(&path, flags.dereference.0, flags.permission) {
+            Ok(res) => res,
+            Err(err) => {
+                print_error!("{}: {}.", path.display(), err);
+                return Ok((None, ExitCode::MinorIssue));
             }
+        };
+
+        // skip files for --tree -d
+        if flags.layout == Layout::Tree
+            && flags.display == Display::DirectoryOnly
+            && !entry.file_type()?.is_dir()
+        {
+            return Ok((None, ExitCode::OK));
+        }
+
+        let mut exit_code = ExitCode::OK;
+
+        let is_mount_point = entry_meta.is_mount_point(parent_device);
+        if is_mount_point {
+            entry_meta.indicator = Indicator::mount_point();
+        }
 
-            let mut entry_meta = match Self::from_path(&path, flags.dereference.0, flags.permission)
-            {
-                Ok(res) => res,
+        // check dereferencing, and stay on the same filesystem for -x
+        if (flags.dereference.0 || !matches!(entry_meta.file_type, FileType::SymLink { .. }))
+            && !(is_mount_point && flags.one_file_system.0)
+        {
+            match entry_meta.recurse_into(depth - 1, flags, cache, ignore) {
+                Ok((content, rec_exit_code)) => {
+                    entry_meta.content = content;
+                    exit_code.set_if_greater(rec_exit_code);
+                }
                 Err(err) => {
                     print_error!("{}: {}.", path.display(), err);
-                    exit_code.set_if_greater(ExitCode::MinorIssue);
-                    continue;
+                    return Ok((None, ExitCode::MinorIssue));
                 }
             };
+        }
 
-            // skip files for --tree -d
-            if flags.layout == Layout::Tree
-                && flags.display == Display::DirectoryOnly
-                && !entry.file_type()?.is_dir()
-            {
-                continue;
-            }
+        if !entry_meta.matches_filters(flags) && !entry_meta.has_listed_content() {
+            return Ok((None, exit_code));
+        }
 
-            // check dereferencing
-            if flags.dereference.0 || !matches!(entry_meta.file_type, FileType::SymLink { .. }) {
-                match entry_meta.recurse_into(depth - 1, flags, cache) {
-                    Ok((content, rec_exit_code)) => {
-                        entry_meta.content = content;
-                        exit_code.set_if_greater(rec_exit_code);
-                    }
-                    Err(err) => {
-                        print_error!("{}: {}.", path.display(), err);
-                        exit_code.set_if_greater(ExitCode::MinorIssue);
-                        continue;
-                    }
-                };
+        let is_directory = entry.file_type()?.is_dir();
+        if let Some((cache, path)) = &git_path {
+            entry_meta.set_git_status(cache.get_canonical(path, is_directory), flags);
+            entry_meta
+                .name
+                .set_submodule(is_directory && cache.is_submodule(path));
+            if flags.blocks.displays_git_diff() {
+                entry_meta.git_diff = cache.diff_stat(path);
             }
+        }
+        entry_meta.read_block_data(flags);
+
+        Ok((Some(entry_meta), exit_code))
+    }
 
-            let is_directory = entry.file_type()?.is_dir();
-            entry_meta.git_status =
-                cache.and_then(|cache| cache.get(&entry_meta.path, is_directory));
-            content.push(entry_meta);
+    /// Whether this path argument is listed with the `--include-glob`, `--type` and `--ext`
+    /// filters. Directories are always listed, the filters applying to their content.
+    pub fn matches_argument_filters(&self, flags: &Flags) -> bool {
+        self.file_type.is_dirlike() || self.matches_filters(flags)
+    }
+
+    /// Whether this entry matches the `--include-glob`, `--type` and `--ext` filters.
+    fn matches_filters(&self, flags: &Flags) -> bool {
+        flags.include_globs.matches(&self.name.name)
+            && flags.types.matches(self.file_type)
+            && flags.extensions.matches(self.name.extension())
+    }
+
+    /// Whether some entries, other than `.` and `..`, were kept while recursing into this one.
+    ///
+    /// Directories not matching the filters are still listed when they lead to matching entries.
+    fn has_listed_content(&self) -> bool {
+        self.content.as_ref().is_some_and(|content| {
+            content
+                .iter()
+                .any(|meta| meta.name.name != "." && meta.name.name != "..")
+        })
+    }
+
+    /// Remove the entries not matching the `--min-size`, `--max-size`, `--newer`, `--older`,
+    /// `--user` and `--group` predicates from the content, recursively.
+    ///
+    /// This runs once the total sizes are known, so that directories are compared by their
+    /// total size when it is computed.
+    pub fn retain_matching(&mut self, flags: &Flags) {
+        if let Some(content) = &mut self.content {
+            content.retain_mut(|meta| {
+                if meta.name.name == "." || meta.name.name == ".." {
+                    return true;
+                }
+                meta.retain_matching(flags);
+                meta.matches_predicates(flags) || meta.has_listed_content()
+            });
         }
+    }
 
-        Ok((Some(content), exit_code))
+    /// Whether this path argument is listed with the predicates. Like with the filters,
+    /// directories are always listed.
+    pub fn matches_argument_predicates(&self, flags: &Flags) -> bool {
+        self.file_type.is_dirlike() || self.matches_predicates(flags)
     }
 
-    pub fn calculate_total_size(&mut self) {
-        if self.size.is_none() {
-            return;
+    fn matches_predicates(&self, flags: &Flags) -> bool {
+        let predicates = &flags.predicates;
+        let size = self.size.map(|size| size.get(flags.size_mode));
+        let date = match self.date(flags.time) {
+            Some(Date::Date(date)) => Some(date),
+            _ => None,
+        };
+
+        predicates
+            .min_size
+            .is_none_or(|min| size.is_some_and(|size| size >= min))
+            && predicates
+                .max_size
+                .is_none_or(|max| size.is_some_and(|size| size <= max))
+            && predicates
+                .newer
+                .is_none_or(|newer| date.is_some_and(|date| *date > newer))
+            && predicates
+                .older
+                .is_none_or(|older| date.is_some_and(|date| *date < older))
+            && predicates
+                .user
+                .as_ref()
+                .is_none_or(|user| self.owner.as_ref().is_some_and(|owner| owner.is_user(user)))
+            && predicates.group.as_ref().is_none_or(|group| {
+                self.owner
+                    .as_ref()
+                    .is_some_and(|owner| owner.is_group(group))
+            })
+    }
+
+    /// Set the Git status of this entry, dimming its name when it is ignored and the git block
+    /// or `--git-dim-ignored` is used.
+    pub fn set_git_status(&mut self, status: Option<GitFileStatus>, flags: &Flags) {
+        let dim = flags.git_dim_ignored.0 || flags.blocks.contains_git_status();
+        let ignored = dim && status.is_some_and(|status| status.workdir == GitStatus::Ignored);
+        self.name.set_git_ignored(ignored);
+        self.git_status = status;
+    }
+
+    /// Read the `chattr` flags, the MIME type, the entry count and the content hash of this entry,
+    /// when they are displayed or sorted on, as they are too costly to read for every entry.
+    pub fn read_block_data(&mut self, flags: &Flags) {
+        if flags.blocks.0.contains(&Block::InodeFlags) {
+            self.inode_flags = InodeFlags::for_path(&self.path, self.file_type);
+        }
+        if flags.sniff.0 || flags.blocks.0.contains(&Block::Mime) {
+            self.name.set_mime(Mime::sniff(&self.path, self.file_type));
+        }
+        if (flags.blocks.0.contains(&Block::Entries) || flags.dir_count.0) && self.entries.is_none()
+        {
+            self.entries = Entries::for_dir(&self.path, self.file_type, flags.display);
+        }
+        if flags.blocks.needs_hash(flags.sorting.column) {
+            let size = self.size.as_ref().map_or(0, Size::get_bytes);
+            self.content_hash = ContentHash::for_path(&self.path, self.file_type, size, flags.hash);
         }
+    }
 
-        if let FileType::Directory { .. } = self.file_type {
-            if let Some(metas) = &mut self.content {
-                let mut size_accumulated = match &self.size {
-                    Some(size) => size.get_bytes(),
-                    None => 0,
-                };
-                for x in &mut metas.iter_mut() {
-                    // must not count the size of '.' and '..', or will be infinite loop
-                    if x.name.name == "." || x.name.name == ".." {
-                        continue;
-                    }
+    /// The timestamp selected by the [TimeField], if it could be read.
+    pub fn date(&self, field: TimeField) -> Option<&Date> {
+        self.dates.as_ref().map(|dates| dates.get(field))
+    }
+
+    /// The id of the device holding this entry, if known.
+    pub fn device(&self) -> Option<u64> {
+        self.inode.and_then(|inode| inode.device())
+    }
+
+    /// Whether this is a directory on another device than its parent on `parent_device`.
+    fn is_mount_point(&self, parent_device: Option<u64>) -> bool {
+        matches!(self.file_type, FileType::Directory { .. })
+            && self.device().is_some()
+            && parent_device.is_some()
+            && self.device() != parent_device
+    }
+
+    /// Mark this entry with the mountpoint indicator if it is on another device than its parent
+    /// directory.
+    pub fn mark_mount_point(&mut self) {
+        let parent_device = self
+            .path
+            .join(Component::ParentDir)
+            .metadata()
+            .ok()
+            .as_ref()
+            .and_then(size_accumulator::device);
+        if self.is_mount_point(parent_device) {
+            self.indicator = Indicator::mount_point();
+        }
+    }
+
+    pub fn calculate_total_size(&mut self, accumulator: &SizeAccumulator) {
+        self.total_tree_size(accumulator);
+    }
+
+    /// Set the total size of this directory and of the directories it holds, returning the
+    /// [TreeSize] this entry adds to its parent.
+    ///
+    /// Each directory counts the files it holds through several hard links once, so that its
+    /// total does not depend on the order in which the threads walk the tree.
+    fn total_tree_size(&mut self, accumulator: &SizeAccumulator) -> TreeSize {
+        let Some(size) = self.size else {
+            return TreeSize::default();
+        };
+        if !matches!(self.file_type, FileType::Directory { .. }) {
+            return self.file_tree_size(size);
+        }
 
-                    x.calculate_total_size();
-                    size_accumulated += match &x.size {
-                        Some(size) => size.get_bytes(),
-                        None => 0,
-                    };
+        let device = self.device();
+        let tree_size = if let Some(metas) = &mut self.content {
+            let content_size = |x: &mut Meta| {
+                // must not count the size of '.' and '..', or will be infinite loop
+                if x.name.name == "." || x.name.name == ".." {
+                    return TreeSize::default();
                 }
-                self.size = Some(Size::new(size_accumulated));
+                if accumulator.skips_device(x.device(), device) {
+                    return TreeSize::default();
+                }
+
+                x.total_tree_size(accumulator)
+            };
+            let content_size = if accumulator.parallel {
+                metas
+                    .par_iter_mut()
+                    .map(content_size)
+                    .reduce(TreeSize::default, TreeSize::merge)
             } else {
-                // possibility that 'depth' limited the recursion in 'recurse_into'
-                self.size = Some(Size::new(Meta::calculate_total_file_size(&self.path)));
-            }
+                metas
+                    .iter_mut()
+                    .map(content_size)
+                    .fold(TreeSize::default(), TreeSize::merge)
+            };
+            TreeSize::new(size).merge(content_size)
+        } else {
+            // possibility that 'depth' limited the recursion in 'recurse_into'
+            Meta::calculate_total_file_size(&self.path, accumulator, device)
+        };
+        self.size = Some(tree_size.total());
+        tree_size
+    }
+
+    /// The [TreeSize] of a file, kept apart by its inode when it has more than one link.
+    fn file_tree_size(&self, size: Size) -> TreeSize {
+        let has_hard_links = matches!(self.file_type, FileType::File { .. })
+            && self
+                .links
+                .and_then(|links| links.count())
+                .is_some_and(|count| count > 1);
+        let key = self
+            .path
+            .symlink_metadata()
+            .ok()
+            .as_ref()
+            .and_then(hard_link_key)
+            .filter(|_| has_hard_links);
+
+        let mut tree_size = TreeSize::default();
+        match key {
+            Some(key) => tree_size.add_hard_link(key, size),
+            None => tree_size = TreeSize::new(size),
         }
+        tree_size
     }
 
-    fn calculate_total_file_size(path: &Path) -> u64 {
+    /// The total size of a path, left out when it is not on `parent_device` for -x.
+    fn calculate_total_file_size(
+        path: &Path,
+        accumulator: &SizeAccumulator,
+        parent_device: Option<u64>,
+    ) -> TreeSize {
         let metadata = path.symlink_metadata();
         let metadata = match metadata {
             Ok(meta) => meta,
             Err(err) => {
                 print_error!("{}: {}.", path.display(), err);
-                return 0;
+                return TreeSize::default();
             }
         };
+        let device = size_accumulator::device(&metadata);
+        if accumulator.skips_device(device, parent_device) {
+            return TreeSize::default();
+        }
         let file_type = metadata.file_type();
         if file_type.is_file() {
-            metadata.len()
+            TreeSize::file(&metadata)
         } else if file_type.is_dir() {
-            let mut size = metadata.len();
+            let (sizes, subdirs) = match accumulator.cached(path, &metadata) {
+                Some(sizes) => {
+                    let subdirs = sizes.subdirs.iter().map(|name| path.join(name)).collect();
+                    (sizes, subdirs)
+                }
+                None => Meta::read_dir_sizes(path, &metadata, accumulator),
+            };
+
+            let mut tree_size = TreeSize::new(sizes.size);
+            for &(dev, ino, size) in &sizes.hard_links {
+                tree_size.add_hard_link((dev, ino), size);
+            }
+
+            let subdir_size =
+                |path: &PathBuf| Meta::calculate_total_file_size(path, accumulator, device);
+            let subdirs_size = if accumulator.parallel {
+                subdirs
+                    .par_iter()
+                    .map(subdir_size)
+                    .reduce(TreeSize::default, TreeSize::merge)
+            } else {
+                subdirs
+                    .iter()
+                    .map(subdir_size)
+                    .fold(TreeSize::default(), TreeSize::merge)
+            };
+
+            tree_size.merge(subdirs_size)
+        } else {
+            TreeSize::default()
+        }
+    }
 
-            let entries = match path.read_dir() {
-                Ok(entries) => entries,
+    /// Stat the entries of a directory, without recursing into its subdirectories.
+    ///
+    /// The result is stored in the cache of the accumulator unless some entry could not be read.
+    fn read_dir_sizes(
+        path: &Path,
+        metadata: &Metadata,
+        accumulator: &SizeAccumulator,
+    ) -> (DirSizes, Vec<PathBuf>) {
+        let mut sizes = DirSizes {
+            size: Size::from(metadata),
+            ..Default::default()
+        };
+        let mut subdirs = Vec::new();
+
+        let entries = match path.read_dir() {
+            Ok(entries) => entries,
+            Err(err) => {
+                print_error!("{}: {}.", path.display(), err);
+                return (sizes, subdirs);
+            }
+        };
+
+        let mut complete = true;
+        for entry in entries {
+            let entry_path = match entry {
+                Ok(entry) => entry.path(),
                 Err(err) => {
                     print_error!("{}: {}.", path.display(), err);
-                    return size;
+                    complete = false;
+                    continue;
                 }
             };
-            for entry in entries {
-                let path = match entry {
-                    Ok(entry) => entry.path(),
-                    Err(err) => {
-                        print_error!("{}: {}.", path.display(), err);
-                        continue;
+            let entry_metadata = match entry_path.symlink_metadata() {
+                Ok(meta) => meta,
+                Err(err) => {
+                    print_error!("{}: {}.", entry_path.display(), err);
+                    complete = false;
+                    continue;
+                }
+            };
+
+            let file_type = entry_metadata.file_type();
+            if file_type.is_file() {
+                match hard_link_key(&entry_metadata) {
+                    Some((dev, ino)) => {
+                        sizes
+                            .hard_links
+                            .push((dev, ino, Size::from(&entry_metadata)))
                     }
-                };
-                size += Meta::calculate_total_file_size(&path);
+                    None => sizes.size = sizes.size + Size::from(&entry_metadata),
+                }
+            } else if file_type.is_dir() {
+                match entry_path.file_name().and_then(|name| name.to_str()) {
+                    Some(name) => sizes.subdirs.push(name.to_string()),
+                    // the cache only stores UTF-8 names
+                    None => complete = false,
+                }
+                subdirs.push(entry_path);
             }
-            size
-        } else {
-            0
         }
+
+        if complete {
+            accumulator.store(path, metadata, &sizes);
+        }
+
+        (sizes, subdirs)
     }
 
     pub fn from_path(
@@ -334,14 +730,14 @@ impl Meta {
 
         let name = Name::new(path, file_type);
 
-        let (inode, links, size, date, owner, permissions_or_attributes, access_control) =
+        let (inode, links, size, dates, owner, permissions_or_attributes, access_control) =
             match broken_link {
                 true => (None, None, None, None, None, None, None),
                 false => (
                     Some(INode::from(&metadata)),
                     Some(Links::from(&metadata)),
                     Some(Size::from(&metadata)),
-                    Some(Date::from(&metadata)),
+                    Some(Timestamps::from(&metadata)),
                     Some(owner),
                     Some(permissions_or_attributes),
                     Some(AccessControl::for_path(path)),
@@ -354,7 +750,7 @@ impl Meta {
             path: path.to_path_buf(),
             symlink: SymLink::from(path),
             size,
-            date,
+            dates,
             indicator: Indicator::from(file_type),
             owner: owner.unwrap_or_default(),
             permissions_or_attributes: permissions_or_attributes.unwrap_or_default(),
@@ -363,15 +759,25 @@ impl Meta {
             content: None,
             access_control,
             git_status: None,
+            git_commit: None,
+            git_diff: None,
+            repo_summary: None,
+            inode_flags: None,
+            content_hash: None,
+            entries: None,
         })
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::flags::PermissionFlag;
+    use crate::app::Cli;
+    use crate::config_file::Config;
+    use crate::flags::{Flags, PermissionFlag, Threads};
 
-    use super::Meta;
+    use super::{Meta, SizeAccumulator};
+    use assert_fs::prelude::*;
+    use clap::Parser;
     use std::{
         fs::File,
         io::{BufWriter, Write},
@@ -423,7 +829,7 @@ mod tests {
             meta_a.inode.is_some()
                 && meta_a.links.is_some()
                 && meta_a.size.is_some()
-                && meta_a.date.is_some()
+                && meta_a.dates.is_some()
                 && meta_a.owner.is_some()
                 && meta_a.permissions_or_attributes.is_some()
                 && meta_a.access_control.is_some()
@@ -433,20 +839,73 @@ mod tests {
             meta_b.inode.is_none()
                 && meta_b.links.is_none()
                 && meta_b.size.is_none()
-                && meta_b.date.is_none()
+                && meta_b.dates.is_none()
                 && meta_b.owner.is_none()
                 && meta_b.permissions_or_attributes.is_none()
                 && meta_b.access_control.is_none()
         );
     }
 
+    #[test]
+    fn test_recurse_into_parallel_matches_sequential() {
+        fn names(metas: &[Meta]) -> Vec<(String, Vec<String>)> {
+            let mut names: Vec<_> = metas
+                .iter()
+                .map(|meta| {
+                    let mut content: Vec<String> = meta
+                        .content
+                        .iter()
+                        .flatten()
+                        .map(|m| m.name.name.clone())
+                        .collect();
+                    content.sort();
+                    (meta.name.name.clone(), content)
+                })
+                .collect();
+            names.sort();
+            names
+        }
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        for d in ["a", "b", "c"] {
+            dir.child(d).create_dir_all().unwrap();
+            for f in ["1", "2", "3"] {
+                dir.child(format!("{d}/{f}")).touch().unwrap();
+            }
+        }
+
+        let cli = Cli::try_parse_from(["lsd", "--tree"]).unwrap();
+        let mut flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+        let mut meta = Meta::from_path(dir.path(), false, PermissionFlag::Rwx).unwrap();
+
+        let (sequential, _) = meta.recurse_into(42, &flags, None, None).unwrap();
+
+        flags.threads = Threads(4);
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(4)
+            .build()
+            .unwrap();
+        let (parallel, _) = pool.install(|| meta.recurse_into(42, &flags, None, None).unwrap());
+
+        assert_eq!(names(&sequential.unwrap()), names(&parallel.unwrap()));
+    }
+
     #[test]
     fn test_calculate_total_file_size_empty() {
         let dir = assert_fs::TempDir::new().unwrap();
         let path_file = dir.path().join("100B-text.txt");
         File::create(&path_file).expect("failed to create file");
 
-        assert!(Meta::calculate_total_file_size(path_file.as_path()) == 0);
+        assert!(
+            Meta::calculate_total_file_size(
+                path_file.as_path(),
+                &SizeAccumulator::new(&Flags::default()),
+                None,
+            )
+            .total()
+            .get_bytes()
+                == 0
+        );
     }
 
     #[test]
@@ -466,6 +925,139 @@ mod tests {
             .flush()
             .expect("failed to write all bytes to file");
 
-        assert!(Meta::calculate_total_file_size(path_file.as_path()) == 100);
+        assert!(
+            Meta::calculate_total_file_size(
+                path_file.as_path(),
+                &SizeAccumulator::new(&Flags::default()),
+                None,
+            )
+            .total()
+            .get_bytes()
+                == 100
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_calculate_total_size_hard_links_counted_once() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("a/file").write_binary(&[0u8; 100]).unwrap();
+        dir.child("b").create_dir_all().unwrap();
+        std::fs::hard_link(dir.child("a/file").path(), dir.child("b/link").path()).unwrap();
+        let dir_size = dir.path().metadata().unwrap().len()
+            + dir.child("a").path().metadata().unwrap().len()
+            + dir.child("b").path().metadata().unwrap().len();
+
+        let accumulator = SizeAccumulator::new(&Flags::default());
+        assert_eq!(
+            dir_size + 100,
+            Meta::calculate_total_file_size(dir.path(), &accumulator, None)
+                .total()
+                .get_bytes()
+        );
+
+        // the same result when the tree was already listed
+        let cli = Cli::try_parse_from(["lsd", "--tree", "--total-size"]).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+        let mut meta = Meta::from_path(dir.path(), false, PermissionFlag::Rwx).unwrap();
+        meta.content = meta.recurse_into(42, &flags, None, None).unwrap().0;
+        meta.calculate_total_size(&SizeAccumulator::new(&flags));
+        assert_eq!(dir_size + 100, meta.size.unwrap().get_bytes());
+    }
+
+    #[test]
+    fn test_recurse_into_counts_entries() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("a.txt").touch().unwrap();
+        dir.child("b.rs").touch().unwrap();
+        dir.child(".hidden").touch().unwrap();
+
+        let cli = Cli::try_parse_from(["lsd", "--blocks", "entries,name", "-I", "*.txt"]).unwrap();
+        let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+        let mut meta = Meta::from_path(dir.path(), false, PermissionFlag::Rwx).unwrap();
+        let content = meta.recurse_into(1, &flags, None, None).unwrap().0.unwrap();
+
+        // the count is read along with the content, the ignored entry still being counted
+        assert_eq!(1, content.len());
+        assert_eq!(Some(2), meta.entries.map(|entries| entries.count()));
+    }
+
+    #[cfg(not(feature = "no-git"))]
+    #[test]
+    fn test_git_hide_ignored() {
+        use crate::git::GitCache;
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        git2::Repository::init(dir.path()).unwrap();
+        dir.child(".gitignore").write_str("target/\n").unwrap();
+        dir.child("target/out").touch().unwrap();
+        dir.child("kept").touch().unwrap();
+        let cache = GitCache::new(dir.path());
+        let meta = Meta::from_path(dir.path(), false, PermissionFlag::Rwx).unwrap();
+
+        let names = |argv: &[&str]| {
+            let cli = Cli::try_parse_from(argv).unwrap();
+            let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+            let content = meta
+                .clone()
+                .recurse_into(1, &flags, Some(&cache), None)
+                .unwrap()
+                .0;
+            let mut names: Vec<String> = content
+                .unwrap()
+                .into_iter()
+                .map(|meta| meta.name.name)
+                .collect();
+            names.sort();
+            names
+        };
+        assert_eq!(vec!["kept", "target"], names(&["lsd"]));
+        assert_eq!(vec!["kept"], names(&["lsd", "--git-hide-ignored"]));
+    }
+
+    #[cfg(not(feature = "no-git"))]
+    #[test]
+    fn test_git_dim_ignored() {
+        use crate::color::{Colors, ThemeOption};
+        use crate::flags::{HyperlinkOption, IconOption, IconTheme};
+        use crate::git::GitCache;
+        use crate::icon::Icons;
+        use crate::meta::name::DisplayOption;
+        use crossterm::style::{Color, Stylize};
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        git2::Repository::init(dir.path()).unwrap();
+        dir.child(".gitignore").write_str("target/\n").unwrap();
+        dir.child("target/out").touch().unwrap();
+        let cache = GitCache::new(dir.path());
+        let meta = Meta::from_path(dir.path(), false, PermissionFlag::Rwx).unwrap();
+        let colors = Colors::new(ThemeOption::NoLscolors);
+        let icons = Icons::new(false, IconOption::Never, IconTheme::Fancy, " ".to_string());
+
+        let render = |argv: &[&str]| {
+            let cli = Cli::try_parse_from(argv).unwrap();
+            let flags = Flags::configure_from(&cli, &Config::with_none()).unwrap();
+            let content = meta
+                .clone()
+                .recurse_into(1, &flags, Some(&cache), None)
+                .unwrap()
+                .0;
+            let target = content
+                .unwrap()
+                .into_iter()
+                .find(|meta| meta.name.name == "target")
+                .unwrap();
+            target.name.render(
+                &colors,
+                &icons,
+                &DisplayOption::FileName,
+                HyperlinkOption::Never,
+                true,
+            )
+        };
+        let dimmed = "target".to_string().with(Color::AnsiValue(240));
+        assert_ne!(dimmed, render(&["lsd", "--blocks", "git-commit,name"]));
+        assert_eq!(dimmed, render(&["lsd", "--blocks", "git,name"]));
+        assert_eq!(dimmed, render(&["lsd", "--git-dim-ignored"]));
     }
 }
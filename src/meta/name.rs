@@ -2,6 +2,7 @@ use crate::color::{ColoredString, Colors, Elem};
 use crate::flags::HyperlinkOption;
 use crate::icon::Icons;
 use crate::meta::filetype::FileType;
+use crate::meta::mime::Mime;
 use crate::print_error;
 use crate::url::Url;
 use std::cmp::{Ordering, PartialOrd};
@@ -21,6 +22,9 @@ pub struct Name {
     path: PathBuf,
     extension: Option<String>,
     file_type: FileType,
+    mime: Option<Mime>,
+    submodule: bool,
+    git_ignored: bool,
 }
 
 impl Name {
@@ -39,6 +43,9 @@ impl Name {
             path: PathBuf::from(path),
             extension,
             file_type,
+            mime: None,
+            submodule: false,
+            git_ignored: false,
         }
     }
 
@@ -185,7 +192,18 @@ impl Name {
             },
         };
 
-        colors.colorize_using_path(content, &self.path, &elem)
+        // the ignored entries are dimmed, whatever their type
+        if self.git_ignored {
+            return colors.colorize(content, &Elem::GitIgnoredName);
+        }
+
+        // the extension says more than the content, as LS_COLORS does
+        match self.mime {
+            Some(mime) if self.extension.is_none() => {
+                colors.colorize_using_mime(content, &self.path, &mime, &elem)
+            }
+            _ => colors.colorize_using_path(content, &self.path, &elem),
+        }
     }
 
     pub fn extension(&self) -> Option<&str> {
@@ -195,6 +213,29 @@ impl Name {
     pub fn file_type(&self) -> FileType {
         self.file_type
     }
+
+    /// The type guessed from the content of the file, when it was sniffed.
+    pub fn mime(&self) -> Option<Mime> {
+        self.mime
+    }
+
+    pub fn set_mime(&mut self, mime: Option<Mime>) {
+        self.mime = mime;
+    }
+
+    /// Whether this directory is a Git submodule, when the Git status was read.
+    pub fn is_submodule(&self) -> bool {
+        self.submodule
+    }
+
+    pub fn set_submodule(&mut self, submodule: bool) {
+        self.submodule = submodule;
+    }
+
+    /// Dim the name of an entry ignored by Git.
+    pub fn set_git_ignored(&mut self, git_ignored: bool) {
+        self.git_ignored = git_ignored;
+    }
 }
 
 impl Ord for Name {
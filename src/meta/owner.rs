@@ -1,5 +1,6 @@
 use crate::Flags;
 use crate::color::{ColoredString, Colors, Elem};
+use crate::flags::predicates::OwnerId;
 #[cfg(unix)]
 use std::fs::Metadata;
 #[cfg(unix)]
@@ -13,6 +14,26 @@ pub struct Cache {
     groups: UsersCache,
 }
 
+impl Cache {
+    /// The name of the user with this id, or the id itself when it has no name.
+    pub fn user_name(&self, uid: u32) -> String {
+        #[cfg(unix)]
+        if let Some(user) = self.users.get_user_by_uid(uid) {
+            return user.name().to_string_lossy().to_string();
+        }
+        uid.to_string()
+    }
+
+    /// The name of the group with this id, or the id itself when it has no name.
+    pub fn group_name(&self, gid: u32) -> String {
+        #[cfg(unix)]
+        if let Some(group) = self.groups.get_group_by_gid(gid) {
+            return group.name().to_string_lossy().to_string();
+        }
+        gid.to_string()
+    }
+}
+
 #[cfg(unix)]
 #[derive(Clone, Debug, Default)]
 pub struct Owner {
@@ -63,20 +84,56 @@ fn truncate(input: &str, after: Option<usize>, marker: Option<String>) -> String
 }
 
 impl Owner {
+    #[cfg(unix)]
+    pub fn uid(&self) -> u32 {
+        self.user
+    }
+
+    #[cfg(unix)]
+    pub fn gid(&self) -> u32 {
+        self.group
+    }
+
     // allow unused variables because cache is used in unix, maybe we can cache for windows in the future
     #[allow(unused_variables)]
-    pub fn render_user(&self, colors: &Colors, cache: &Cache, flags: &Flags) -> ColoredString {
+    pub fn user_name(&self, cache: &Cache) -> String {
         #[cfg(unix)]
-        let user = &match cache.users.get_user_by_uid(self.user) {
-            Some(user) => user.name().to_string_lossy().to_string(),
-            None => self.user.to_string(),
-        };
+        {
+            cache.user_name(self.user)
+        }
         #[cfg(windows)]
-        let user = &self.user;
+        {
+            self.user.clone()
+        }
+    }
 
+    // allow unused variables because cache is used in unix, maybe we can cache for windows in the future
+    #[allow(unused_variables)]
+    pub fn group_name(&self, cache: &Cache) -> String {
+        #[cfg(unix)]
+        {
+            cache.group_name(self.group)
+        }
+        #[cfg(windows)]
+        {
+            self.group.clone()
+        }
+    }
+
+    /// Whether the file is owned by the `user` of the `--user` condition.
+    pub fn is_user(&self, user: &OwnerId) -> bool {
+        self.user == *user
+    }
+
+    /// Whether the file belongs to the `group` of the `--group` condition.
+    pub fn is_group(&self, group: &OwnerId) -> bool {
+        self.group == *group
+    }
+
+    pub fn render_user(&self, colors: &Colors, cache: &Cache, flags: &Flags) -> ColoredString {
         colors.colorize(
             truncate(
-                user,
+                &self.user_name(cache),
                 flags.truncate_owner.after,
                 flags.truncate_owner.marker.clone(),
             ),
@@ -84,20 +141,10 @@ impl Owner {
         )
     }
 
-    // allow unused variables because cache is used in unix, maybe we can cache for windows in the future
-    #[allow(unused_variables)]
     pub fn render_group(&self, colors: &Colors, cache: &Cache, flags: &Flags) -> ColoredString {
-        #[cfg(unix)]
-        let group = &match cache.groups.get_group_by_gid(self.group) {
-            Some(group) => group.name().to_string_lossy().to_string(),
-            None => self.group.to_string(),
-        };
-        #[cfg(windows)]
-        let group = &self.group;
-
         colors.colorize(
             truncate(
-                group,
+                &self.group_name(cache),
                 flags.truncate_owner.after,
                 flags.truncate_owner.marker.clone(),
             ),
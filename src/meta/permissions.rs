@@ -26,7 +26,19 @@ impl From<&Metadata> for Permissions {
     fn from(meta: &Metadata) -> Self {
         use std::os::unix::fs::PermissionsExt;
 
-        let bits = meta.permissions().mode();
+        Self::from_mode(meta.permissions().mode())
+    }
+
+    #[cfg(windows)]
+    fn from(_: &Metadata) -> Self {
+        panic!("Cannot get permissions from metadata on Windows")
+    }
+}
+
+impl Permissions {
+    /// The permissions given by the bits of a Unix mode, like `0o755`.
+    #[cfg(unix)]
+    pub fn from_mode(bits: u32) -> Self {
         let has_bit = |bit| bits & bit == bit;
 
         Self {
@@ -48,80 +60,76 @@ impl From<&Metadata> for Permissions {
         }
     }
 
-    #[cfg(windows)]
-    fn from(_: &Metadata) -> Self {
-        panic!("Cannot get permissions from metadata on Windows")
-    }
-}
-
-impl Permissions {
     fn bits_to_octal(r: bool, w: bool, x: bool) -> u8 {
         (r as u8) * 4 + (w as u8) * 2 + (x as u8)
     }
 
-    pub fn render(&self, colors: &Colors, flags: &Flags) -> ColoredString {
-        let bit = |bit, chr: &'static str, elem: &Elem| {
+    /// The nine `ls`-style permission characters with the element used to color each of them.
+    fn rwx_symbols(&self) -> [(char, Elem); 9] {
+        let bit = |bit, chr, elem| {
             if bit {
-                colors.colorize(chr, elem)
+                (chr, elem)
             } else {
-                colors.colorize('-', &Elem::NoAccess)
+                ('-', Elem::NoAccess)
             }
         };
+        let special = |exec, special, set: char, unset: char| match (exec, special) {
+            (false, false) => ('-', Elem::NoAccess),
+            (true, false) => ('x', Elem::Exec),
+            (false, true) => (unset, Elem::ExecSticky),
+            (true, true) => (set, Elem::ExecSticky),
+        };
+
+        [
+            // User permissions
+            bit(self.user_read, 'r', Elem::Read),
+            bit(self.user_write, 'w', Elem::Write),
+            special(self.user_execute, self.setuid, 's', 'S'),
+            // Group permissions
+            bit(self.group_read, 'r', Elem::Read),
+            bit(self.group_write, 'w', Elem::Write),
+            special(self.group_execute, self.setgid, 's', 'S'),
+            // Other permissions
+            bit(self.other_read, 'r', Elem::Read),
+            bit(self.other_write, 'w', Elem::Write),
+            special(self.other_execute, self.sticky, 't', 'T'),
+        ]
+    }
 
+    /// The permissions as an uncolored `rwxr-xr-x` string.
+    pub fn rwx(&self) -> String {
+        self.rwx_symbols().iter().map(|(chr, _)| chr).collect()
+    }
+
+    /// The permissions as a four digit octal string, e.g. `0755`.
+    pub fn octal(&self) -> String {
+        [
+            Self::bits_to_octal(self.setuid, self.setgid, self.sticky),
+            Self::bits_to_octal(self.user_read, self.user_write, self.user_execute),
+            Self::bits_to_octal(self.group_read, self.group_write, self.group_execute),
+            Self::bits_to_octal(self.other_read, self.other_write, self.other_execute),
+        ]
+        .into_iter()
+        .fold(String::with_capacity(4), |mut acc, x| {
+            acc.push(
+                char::from_digit(x as u32, 8)
+                    .expect("octal value of permission should not be greater than 7"),
+            );
+            acc
+        })
+    }
+
+    pub fn render(&self, colors: &Colors, flags: &Flags) -> ColoredString {
         let res = match flags.permission {
-            PermissionFlag::Rwx => [
-                // User permissions
-                bit(self.user_read, "r", &Elem::Read),
-                bit(self.user_write, "w", &Elem::Write),
-                match (self.user_execute, self.setuid) {
-                    (false, false) => colors.colorize('-', &Elem::NoAccess),
-                    (true, false) => colors.colorize('x', &Elem::Exec),
-                    (false, true) => colors.colorize('S', &Elem::ExecSticky),
-                    (true, true) => colors.colorize('s', &Elem::ExecSticky),
-                },
-                // Group permissions
-                bit(self.group_read, "r", &Elem::Read),
-                bit(self.group_write, "w", &Elem::Write),
-                match (self.group_execute, self.setgid) {
-                    (false, false) => colors.colorize('-', &Elem::NoAccess),
-                    (true, false) => colors.colorize('x', &Elem::Exec),
-                    (false, true) => colors.colorize('S', &Elem::ExecSticky),
-                    (true, true) => colors.colorize('s', &Elem::ExecSticky),
-                },
-                // Other permissions
-                bit(self.other_read, "r", &Elem::Read),
-                bit(self.other_write, "w", &Elem::Write),
-                match (self.other_execute, self.sticky) {
-                    (false, false) => colors.colorize('-', &Elem::NoAccess),
-                    (true, false) => colors.colorize('x', &Elem::Exec),
-                    (false, true) => colors.colorize('T', &Elem::ExecSticky),
-                    (true, true) => colors.colorize('t', &Elem::ExecSticky),
-                },
-            ]
-            .into_iter()
-            // From the experiment, the maximum string size is 153 bytes
-            .fold(String::with_capacity(160), |mut acc, x| {
-                acc.push_str(&x.to_string());
-                acc
-            }),
-            PermissionFlag::Octal => {
-                let octals = [
-                    Self::bits_to_octal(self.setuid, self.setgid, self.sticky),
-                    Self::bits_to_octal(self.user_read, self.user_write, self.user_execute),
-                    Self::bits_to_octal(self.group_read, self.group_write, self.group_execute),
-                    Self::bits_to_octal(self.other_read, self.other_write, self.other_execute),
-                ]
+            PermissionFlag::Rwx => self
+                .rwx_symbols()
                 .into_iter()
-                .fold(String::with_capacity(4), |mut acc, x| {
-                    acc.push(
-                        char::from_digit(x as u32, 8)
-                            .expect("octal value of permission should not be greater than 7"),
-                    );
+                // From the experiment, the maximum string size is 153 bytes
+                .fold(String::with_capacity(160), |mut acc, (chr, elem)| {
+                    acc.push_str(&colors.colorize(chr, &elem).to_string());
                     acc
-                });
-
-                colors.colorize(octals, &Elem::Octal).to_string()
-            }
+                }),
+            PermissionFlag::Octal => colors.colorize(self.octal(), &Elem::Octal).to_string(),
             // technically this should be an error, hmm
             PermissionFlag::Attributes => colors.colorize('-', &Elem::NoAccess).to_string(),
             PermissionFlag::Disable => colors.colorize('-', &Elem::NoAccess).to_string(),
@@ -298,6 +306,23 @@ mod test {
         assert_eq!("1777", perms.render(&colors, &flags).content());
     }
 
+    #[test]
+    fn permission_raw_strings() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        // Create the file;
+        let file_path = tmp_dir.path().join("file.txt");
+        File::create(&file_path).expect("failed to create file");
+        fs::set_permissions(&file_path, fs::Permissions::from_mode(0o4751))
+            .expect("unable to set permissions to file");
+        let meta = file_path.metadata().expect("failed to get meta");
+
+        let perms = Permissions::from(&meta);
+
+        assert_eq!("rwsr-x--x", perms.rwx());
+        assert_eq!("4751", perms.octal());
+    }
+
     #[test]
     fn permission_disable() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
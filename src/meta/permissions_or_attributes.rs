@@ -15,6 +15,14 @@ pub enum PermissionsOrAttributes {
 }
 
 impl PermissionsOrAttributes {
+    pub fn permissions(&self) -> Option<&Permissions> {
+        match self {
+            PermissionsOrAttributes::Permissions(permissions) => Some(permissions),
+            #[cfg(windows)]
+            PermissionsOrAttributes::WindowsAttributes(_) => None,
+        }
+    }
+
     pub fn render(&self, colors: &Colors, flags: &Flags) -> ColoredString {
         match self {
             PermissionsOrAttributes::Permissions(permissions) => permissions.render(colors, flags),
@@ -0,0 +1,243 @@
+//! This module defines the summary of a Git repository printed by `--git-summary` before the
+//! listing of a directory inside it.
+
+use super::GitFileStatus;
+use crate::color::{ColoredString, Colors, Elem};
+use crate::git::GitStatus;
+use crate::git_theme::GitTheme;
+
+/// Where HEAD points to.
+#[cfg_attr(feature = "no-git", allow(dead_code))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Head {
+    Branch(String),
+    /// A commit given by its short id, outside of any branch.
+    Detached(String),
+    /// A branch without any commit yet.
+    Unborn(String),
+}
+
+/// The remote branch tracked by the current one, and how far they diverged.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Upstream {
+    pub name: String,
+    pub ahead: usize,
+    pub behind: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RepoSummary {
+    pub head: Head,
+    pub upstream: Option<Upstream>,
+    pub stashes: usize,
+    /// The reduction of the statuses of the files, [None] when the working tree is clean.
+    pub status: Option<GitFileStatus>,
+}
+
+impl RepoSummary {
+    /// Read the summary of `repo`, whose file statuses are already known.
+    ///
+    /// The repository is taken mutably as listing the stashes requires it.
+    #[cfg(not(feature = "no-git"))]
+    pub fn new(repo: &mut git2::Repository, status: Option<GitFileStatus>) -> Self {
+        let mut stashes = 0;
+        // an error only means there is no stash to count
+        let _ = repo.stash_foreach(|_, _, _| {
+            stashes += 1;
+            true
+        });
+
+        let (head, upstream) = match repo.head() {
+            Ok(head) if head.is_branch() => {
+                let name = head.shorthand().unwrap_or("HEAD").to_string();
+                let local = head.target();
+                let upstream = git2::Branch::wrap(head).upstream().ok().and_then(|branch| {
+                    let name = branch.name().ok()??.to_string();
+                    let (ahead, behind) = repo
+                        .graph_ahead_behind(local?, branch.get().target()?)
+                        .ok()?;
+                    Some(Upstream {
+                        name,
+                        ahead,
+                        behind,
+                    })
+                });
+                (Head::Branch(name), upstream)
+            }
+            Ok(head) => {
+                let id = head.target().map(|id| id.to_string()).unwrap_or_default();
+                (Head::Detached(id.chars().take(7).collect()), None)
+            }
+            Err(_) => {
+                let name = repo
+                    .find_reference("HEAD")
+                    .ok()
+                    .and_then(|head| head.symbolic_target().map(String::from))
+                    .map(|target| target.trim_start_matches("refs/heads/").to_string())
+                    .unwrap_or_else(|| "HEAD".to_string());
+                (Head::Unborn(name), None)
+            }
+        };
+
+        Self {
+            head,
+            upstream,
+            stashes,
+            status,
+        }
+    }
+
+    /// Render the summary on one line, like
+    /// `main...origin/main [ahead 2, behind 1], 1 stash, .M`.
+    pub fn render(&self, colors: &Colors, git_theme: &GitTheme) -> String {
+        let head = match &self.head {
+            Head::Branch(name) => name.clone(),
+            Head::Detached(id) => format!("HEAD (detached at {id})"),
+            Head::Unborn(name) => format!("{name} (no commits yet)"),
+        };
+        let mut output = colors.colorize(head, &Elem::GitBranch).to_string();
+
+        if let Some(upstream) = &self.upstream {
+            output += &format!(
+                "...{}",
+                colors.colorize(upstream.name.clone(), &Elem::GitBranch)
+            );
+
+            let mut divergence: Vec<ColoredString> = Vec::new();
+            if upstream.ahead > 0 {
+                let ahead = format!("ahead {}", upstream.ahead);
+                divergence.push(colors.colorize(ahead, &Elem::GitAhead));
+            }
+            if upstream.behind > 0 {
+                let behind = format!("behind {}", upstream.behind);
+                divergence.push(colors.colorize(behind, &Elem::GitBehind));
+            }
+            if !divergence.is_empty() {
+                let divergence: Vec<String> = divergence.iter().map(|d| d.to_string()).collect();
+                output += &format!(" [{}]", divergence.join(", "));
+            }
+        }
+
+        if self.stashes > 0 {
+            let stashes = match self.stashes {
+                1 => "1 stash".to_string(),
+                n => format!("{n} stashes"),
+            };
+            output += &format!(", {}", colors.colorize(stashes, &Elem::GitStash));
+        }
+
+        let status = match &self.status {
+            Some(status) => status.render(colors, git_theme),
+            None => colors.colorize(
+                "clean",
+                &Elem::GitStatus {
+                    status: GitStatus::Unmodified,
+                },
+            ),
+        };
+        output + &format!(", {status}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Head, RepoSummary, Upstream};
+    use crate::color::{Colors, ThemeOption};
+    use crate::git::GitStatus;
+    use crate::git_theme::GitTheme;
+    use crate::meta::GitFileStatus;
+
+    fn render(summary: &RepoSummary) -> String {
+        summary.render(&Colors::new(ThemeOption::NoColor), &GitTheme::new())
+    }
+
+    #[test]
+    fn test_render() {
+        let mut summary = RepoSummary {
+            head: Head::Branch("main".to_string()),
+            upstream: None,
+            stashes: 0,
+            status: None,
+        };
+        assert_eq!("main, clean", render(&summary));
+
+        summary.upstream = Some(Upstream {
+            name: "origin/main".to_string(),
+            ahead: 2,
+            behind: 1,
+        });
+        summary.stashes = 3;
+        summary.status = Some(GitFileStatus {
+            index: GitStatus::Unmodified,
+            workdir: GitStatus::Modified,
+        });
+        assert_eq!(
+            "main...origin/main [ahead 2, behind 1], 3 stashes, .M",
+            render(&summary)
+        );
+
+        summary.head = Head::Detached("abc1234".to_string());
+        summary.upstream = None;
+        summary.stashes = 1;
+        summary.status = None;
+        assert_eq!(
+            "HEAD (detached at abc1234), 1 stash, clean",
+            render(&summary)
+        );
+
+        summary.head = Head::Unborn("main".to_string());
+        summary.stashes = 0;
+        assert_eq!("main (no commits yet), clean", render(&summary));
+    }
+
+    #[cfg(not(feature = "no-git"))]
+    #[test]
+    fn test_read_repository() {
+        use git2::{Repository, RepositoryInitOptions, Signature};
+        use tempfile::tempdir;
+
+        let tmp = tempdir().unwrap();
+        let mut opts = RepositoryInitOptions::new();
+        opts.initial_head("main");
+        let mut repo = Repository::init_opts(tmp.path(), &opts).unwrap();
+        assert_eq!(
+            Head::Unborn("main".to_string()),
+            RepoSummary::new(&mut repo, None).head
+        );
+
+        let sig = Signature::now("name", "email").unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let first = repo
+            .commit(Some("HEAD"), &sig, &sig, "first", &tree, &[])
+            .unwrap();
+        let parent = repo.find_commit(first).unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "second", &tree, &[&parent])
+            .unwrap();
+        drop(parent);
+        drop(tree);
+
+        // the remote branch is one commit behind
+        repo.remote("origin", "https://example.com/repo.git")
+            .unwrap();
+        repo.reference("refs/remotes/origin/main", first, false, "fetch")
+            .unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("branch.main.remote", "origin").unwrap();
+        config
+            .set_str("branch.main.merge", "refs/heads/main")
+            .unwrap();
+
+        let summary = RepoSummary::new(&mut repo, None);
+        assert_eq!(Head::Branch("main".to_string()), summary.head);
+        assert_eq!(
+            Some(Upstream {
+                name: "origin/main".to_string(),
+                ahead: 1,
+                behind: 0,
+            }),
+            summary.upstream
+        );
+        assert_eq!(0, summary.stashes);
+    }
+}
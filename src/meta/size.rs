@@ -1,6 +1,9 @@
 use crate::color::{ColoredString, Colors, Elem};
-use crate::flags::{Flags, SizeFlag};
+use crate::flags::{Flags, SizeFlag, SizeMode};
+use serde::{Deserialize, Serialize};
 use std::fs::Metadata;
+use std::iter::Sum;
+use std::ops::Add;
 
 const KB: u64 = 1024;
 const MB: u64 = 1024_u64.pow(2);
@@ -16,26 +19,71 @@ pub enum Unit {
     Tera,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+/// The apparent size of a file, and the size allocated for it on the disk.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Size {
     bytes: u64,
+    allocated: u64,
 }
 
 impl From<&Metadata> for Size {
+    #[cfg(unix)]
+    fn from(meta: &Metadata) -> Self {
+        use std::os::unix::fs::MetadataExt;
+
+        // st_blocks is always counted in 512-byte units, whatever the block size of the device
+        Self::with_allocated(meta.len(), meta.blocks() * 512)
+    }
+
+    #[cfg(not(unix))]
     fn from(meta: &Metadata) -> Self {
-        Self { bytes: meta.len() }
+        Self::new(meta.len())
+    }
+}
+
+impl Add for Size {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::with_allocated(self.bytes + other.bytes, self.allocated + other.allocated)
+    }
+}
+
+impl Sum for Size {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
     }
 }
 
 impl Size {
+    /// A size whose allocated size is its apparent size.
+    #[cfg(any(test, not(unix)))]
     pub fn new(bytes: u64) -> Self {
-        Self { bytes }
+        Self::with_allocated(bytes, bytes)
     }
 
+    pub fn with_allocated(bytes: u64, allocated: u64) -> Self {
+        Self { bytes, allocated }
+    }
+
+    /// The apparent size, i.e. the length of the file.
     pub fn get_bytes(&self) -> u64 {
         self.bytes
     }
 
+    /// The size of the blocks allocated on the disk.
+    pub fn get_allocated(&self) -> u64 {
+        self.allocated
+    }
+
+    /// The size selected by the [SizeMode].
+    pub fn get(&self, mode: SizeMode) -> u64 {
+        match mode {
+            SizeMode::Apparent => self.bytes,
+            SizeMode::Allocated => self.allocated,
+        }
+    }
+
     fn format_size(&self, number: f64) -> String {
         format!("{0:.1$}", number, if number < 10.0 { 1 } else { 0 })
     }
@@ -45,7 +93,7 @@ impl Size {
             return Unit::Byte;
         }
 
-        match self.bytes {
+        match self.get(flags.size_mode) {
             b if b < KB => Unit::Byte,
             b if b < MB => Unit::Kilo,
             b if b < GB => Unit::Mega,
@@ -86,8 +134,8 @@ impl Size {
         ColoredString::new(Colors::default_style(), res)
     }
 
-    fn paint(&self, colors: &Colors, content: String) -> ColoredString {
-        let bytes = self.get_bytes();
+    fn paint(&self, colors: &Colors, flags: &Flags, content: String) -> ColoredString {
+        let bytes = self.get(flags.size_mode);
 
         let elem = if bytes >= GB {
             &Elem::FileLarge
@@ -103,25 +151,26 @@ impl Size {
     pub fn render_value(&self, colors: &Colors, flags: &Flags) -> ColoredString {
         let content = self.value_string(flags);
 
-        self.paint(colors, content)
+        self.paint(colors, flags, content)
     }
 
     pub fn value_string(&self, flags: &Flags) -> String {
         let unit = self.get_unit(flags);
+        let bytes = self.get(flags.size_mode);
 
         match unit {
-            Unit::Byte => self.bytes.to_string(),
-            Unit::Kilo => self.format_size(((self.bytes as f64 / KB as f64) * 10.0).round() / 10.0),
-            Unit::Mega => self.format_size(((self.bytes as f64 / MB as f64) * 10.0).round() / 10.0),
-            Unit::Giga => self.format_size(((self.bytes as f64 / GB as f64) * 10.0).round() / 10.0),
-            Unit::Tera => self.format_size(((self.bytes as f64 / TB as f64) * 10.0).round() / 10.0),
+            Unit::Byte => bytes.to_string(),
+            Unit::Kilo => self.format_size(((bytes as f64 / KB as f64) * 10.0).round() / 10.0),
+            Unit::Mega => self.format_size(((bytes as f64 / MB as f64) * 10.0).round() / 10.0),
+            Unit::Giga => self.format_size(((bytes as f64 / GB as f64) * 10.0).round() / 10.0),
+            Unit::Tera => self.format_size(((bytes as f64 / TB as f64) * 10.0).round() / 10.0),
         }
     }
 
     pub fn render_unit(&self, colors: &Colors, flags: &Flags) -> ColoredString {
         let content = self.unit_string(flags);
 
-        self.paint(colors, content)
+        self.paint(colors, flags, content)
     }
 
     pub fn unit_string(&self, flags: &Flags) -> String {
@@ -145,13 +194,48 @@ impl Size {
             SizeFlag::Bytes => String::from(""),
         }
     }
+
+    /// Parse a size written like the ones displayed, e.g. `512`, `10K`, `1.5 MB` or `2G`, into
+    /// a number of bytes. Units are powers of 1024, as in the listing.
+    pub fn parse_bytes(value: &str) -> Option<u64> {
+        let value = value.trim();
+        let split = value
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(value.len());
+        let (number, unit) = value.split_at(split);
+        let number: f64 = number.parse().ok()?;
+
+        let multiplier = match unit.trim().to_ascii_uppercase().as_str() {
+            "" | "B" => 1,
+            "K" | "KB" => KB,
+            "M" | "MB" => MB,
+            "G" | "GB" => GB,
+            "T" | "TB" => TB,
+            _ => return None,
+        };
+
+        Some((number * multiplier as f64).round() as u64)
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::{GB, KB, MB, Size, TB};
     use crate::color::{Colors, ThemeOption};
-    use crate::flags::{Flags, SizeFlag};
+    use crate::flags::{Flags, SizeFlag, SizeMode};
+
+    #[test]
+    fn parse_bytes() {
+        assert_eq!(Some(512), Size::parse_bytes("512"));
+        assert_eq!(Some(512), Size::parse_bytes("512B"));
+        assert_eq!(Some(10 * KB), Size::parse_bytes("10K"));
+        assert_eq!(Some(3 * MB / 2), Size::parse_bytes("1.5 MB"));
+        assert_eq!(Some(2 * GB), Size::parse_bytes("2g"));
+        assert_eq!(Some(TB), Size::parse_bytes("1TB"));
+        assert_eq!(None, Size::parse_bytes("1X"));
+        assert_eq!(None, Size::parse_bytes("M"));
+        assert_eq!(None, Size::parse_bytes("-1K"));
+    }
 
     #[test]
     fn render_byte() {
@@ -329,4 +413,30 @@ mod test {
         assert_eq!(size.render(&colors, &flags, Some(2)).to_string(), "42K");
         assert_eq!(size.render(&colors, &flags, Some(3)).to_string(), " 42K");
     }
+
+    #[test]
+    fn render_allocated() {
+        let size = Size::with_allocated(42, 4 * KB); // 42 bytes in a 4 kilobytes block
+        let mut flags = Flags::default();
+
+        assert_eq!(size.value_string(&flags), "42");
+        assert_eq!(size.unit_string(&flags), "B");
+        flags.size_mode = SizeMode::Allocated;
+        assert_eq!(size.value_string(&flags), "4.0");
+        assert_eq!(size.unit_string(&flags), "KB");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn allocated_sparse_file() {
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("sparse");
+        let file = std::fs::File::create(&file_path).expect("failed to create file");
+        file.set_len(10 * MB).expect("failed to extend file");
+
+        let size = Size::from(&file_path.metadata().expect("failed to get meta"));
+
+        assert_eq!(size.get_bytes(), 10 * MB);
+        assert!(size.get_allocated() < size.get_bytes());
+    }
 }
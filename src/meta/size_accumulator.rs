@@ -0,0 +1,429 @@
+//! This module provides the [SizeAccumulator] used to compute the total size of directories.
+//!
+//! It is shared by all the threads walking a tree, and can persist the size of each directory on
+//! disk so that the next listing of an unchanged tree does not have to stat every file again.
+//!
+//! The hard links are deduplicated with a [TreeSize] per directory, so that a file linked from
+//! several places is counted once in the total of each directory holding one of its links,
+//! whatever the order in which the threads walk the tree.
+
+use super::Size;
+use crate::flags::Flags;
+use crate::print_error;
+
+use serde::{Deserialize, Serialize};
+
+use std::collections::HashMap;
+use std::fs::{self, Metadata};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The version of the on-disk cache format, bump it when [CachedDir] changes.
+const CACHE_VERSION: u32 = 3;
+
+/// The entries not used for this long are dropped when the cache is saved.
+const MAX_UNUSED: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// The most entries kept in the cache, the least recently used ones being dropped first.
+const MAX_ENTRIES: usize = 100_000;
+
+/// The last use of an entry is only updated, making the cache dirty, after this long.
+const USE_GRANULARITY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The sizes found directly inside a directory, without its subdirectories.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DirSizes {
+    /// The size of the directory itself and of its files that have a single link.
+    pub size: Size,
+    /// The `(device, inode, size)` of the files having more than one link.
+    pub hard_links: Vec<(u64, u64, Size)>,
+    /// The names of the subdirectories, relative to the directory.
+    pub subdirs: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct CachedDir {
+    /// The modification time of the directory, as seconds and nanoseconds since the epoch.
+    mtime: (u64, u32),
+    /// When the entry was last read or written, as seconds since the epoch.
+    used: u64,
+    sizes: DirSizes,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    version: u32,
+    dirs: HashMap<String, CachedDir>,
+}
+
+/// The on-disk cache of [DirSizes], keyed by canonicalized directory path and modification time.
+///
+/// A directory modification time only changes when entries are added, removed or renamed, so a
+/// file growing in place is not noticed until its directory changes. That is the price of not
+/// stating every file, which is why the cache is opt-in.
+struct SizeCache {
+    path: PathBuf,
+    dirs: Mutex<HashMap<String, CachedDir>>,
+    dirty: AtomicBool,
+}
+
+impl SizeCache {
+    fn default_path() -> Option<PathBuf> {
+        dirs::cache_dir().map(|dir| dir.join("lsd").join("total-size.json"))
+    }
+
+    fn load(path: PathBuf) -> Self {
+        let dirs = fs::read(&path)
+            .ok()
+            .and_then(|content| serde_json::from_slice::<CacheFile>(&content).ok())
+            .filter(|cache| cache.version == CACHE_VERSION)
+            .map(|cache| cache.dirs)
+            .unwrap_or_default();
+
+        Self {
+            path,
+            dirs: Mutex::new(dirs),
+            dirty: AtomicBool::new(false),
+        }
+    }
+
+    /// The key of a directory, the same whatever the current directory and the symbolic links
+    /// it is reached through.
+    fn key(path: &Path) -> Option<String> {
+        fs::canonicalize(path).ok()?.to_str().map(String::from)
+    }
+
+    fn get(&self, path: &Path, mtime: (u64, u32)) -> Option<DirSizes> {
+        let key = Self::key(path)?;
+        let mut dirs = self.dirs.lock().unwrap();
+        let cached = dirs.get_mut(&key).filter(|cached| cached.mtime == mtime)?;
+        let now = now_secs();
+        if now.saturating_sub(cached.used) > USE_GRANULARITY.as_secs() {
+            cached.used = now;
+            self.dirty.store(true, Ordering::Relaxed);
+        }
+        Some(cached.sizes.clone())
+    }
+
+    fn insert(&self, path: &Path, mtime: (u64, u32), sizes: &DirSizes) {
+        // A directory modified within the timestamp granularity could change again without its
+        // modification time changing, do not trust it yet.
+        let recent = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|now| now.saturating_sub(Duration::from_secs(2)) < Duration::new(mtime.0, mtime.1))
+            .unwrap_or(true);
+        if recent {
+            return;
+        }
+        let Some(key) = Self::key(path) else {
+            return;
+        };
+
+        let cached = CachedDir {
+            mtime,
+            used: now_secs(),
+            sizes: sizes.clone(),
+        };
+        self.dirs.lock().unwrap().insert(key, cached);
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    /// Drop the entries not used for [MAX_UNUSED], and the least recently used ones beyond
+    /// [MAX_ENTRIES].
+    fn evict(dirs: &mut HashMap<String, CachedDir>, now: u64) {
+        dirs.retain(|_, cached| now.saturating_sub(cached.used) <= MAX_UNUSED.as_secs());
+        if dirs.len() > MAX_ENTRIES {
+            let mut by_use: Vec<(u64, String)> = dirs
+                .iter()
+                .map(|(key, cached)| (cached.used, key.clone()))
+                .collect();
+            by_use.sort_unstable_by(|a, b| b.cmp(a));
+            for (_, key) in by_use.into_iter().skip(MAX_ENTRIES) {
+                dirs.remove(&key);
+            }
+        }
+    }
+
+    fn save(&self) {
+        if !self.dirty.load(Ordering::Relaxed) {
+            return;
+        }
+
+        let mut dirs = std::mem::take(&mut *self.dirs.lock().unwrap());
+        Self::evict(&mut dirs, now_secs());
+        let cache = CacheFile {
+            version: CACHE_VERSION,
+            dirs,
+        };
+        let write = || -> std::io::Result<()> {
+            if let Some(parent) = self.path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            // Write to a temporary file first so that concurrent runs never read a partial file.
+            let tmp = self
+                .path
+                .with_extension(format!("{}.tmp", std::process::id()));
+            fs::write(&tmp, serde_json::to_vec(&cache)?)?;
+            fs::rename(&tmp, &self.path)
+        };
+        if let Err(err) = write() {
+            print_error!(
+                "Cannot write the size cache {}: {}.",
+                self.path.display(),
+                err
+            );
+        }
+    }
+}
+
+/// The size of a tree, its files having more than one link kept apart by `(device, inode)` so
+/// that each of them is counted once however many of its links the tree holds.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TreeSize {
+    size: Size,
+    hard_links: HashMap<(u64, u64), Size>,
+}
+
+impl TreeSize {
+    pub fn new(size: Size) -> Self {
+        Self {
+            size,
+            hard_links: HashMap::new(),
+        }
+    }
+
+    /// The size of a file, kept apart when it has more than one link.
+    pub fn file(metadata: &Metadata) -> Self {
+        let mut tree_size = Self::default();
+        match hard_link_key(metadata) {
+            Some(key) => tree_size.add_hard_link(key, Size::from(metadata)),
+            None => tree_size.size = Size::from(metadata),
+        }
+        tree_size
+    }
+
+    pub fn add_hard_link(&mut self, key: (u64, u64), size: Size) {
+        self.hard_links.insert(key, size);
+    }
+
+    /// The size of both trees, the files linked from both being counted once.
+    pub fn merge(mut self, other: Self) -> Self {
+        self.size = self.size + other.size;
+        self.hard_links.extend(other.hard_links);
+        self
+    }
+
+    pub fn total(&self) -> Size {
+        self.size + self.hard_links.values().copied().sum()
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |now| now.as_secs())
+}
+
+/// The state shared while computing the total size of directories.
+pub struct SizeAccumulator {
+    /// Whether the directories should be walked by the thread pool.
+    pub parallel: bool,
+    /// Whether the directories on other filesystems should be left out.
+    pub one_file_system: bool,
+    cache: Option<SizeCache>,
+}
+
+impl SizeAccumulator {
+    /// Create an accumulator, loading the on-disk cache from its default location when
+    /// enabled by the [Flags].
+    pub fn new(flags: &Flags) -> Self {
+        let cache = if flags.total_size_cache.0 {
+            SizeCache::default_path().map(SizeCache::load)
+        } else {
+            None
+        };
+
+        Self {
+            parallel: flags.threads.is_parallel(),
+            one_file_system: flags.one_file_system.0,
+            cache,
+        }
+    }
+
+    #[cfg(test)]
+    fn with_cache_file(path: PathBuf) -> Self {
+        Self {
+            parallel: false,
+            one_file_system: false,
+            cache: Some(SizeCache::load(path)),
+        }
+    }
+
+    /// Whether a file on `device` should be left out of a directory on `parent_device`.
+    pub fn skips_device(&self, device: Option<u64>, parent_device: Option<u64>) -> bool {
+        self.one_file_system
+            && device.is_some()
+            && parent_device.is_some()
+            && device != parent_device
+    }
+
+    /// The cached [DirSizes] of a directory, if it did not change since they were stored.
+    pub fn cached(&self, path: &Path, metadata: &Metadata) -> Option<DirSizes> {
+        let cache = self.cache.as_ref()?;
+        cache.get(path, modified(metadata)?)
+    }
+
+    /// Store the [DirSizes] of a directory in the cache, if enabled.
+    pub fn store(&self, path: &Path, metadata: &Metadata, sizes: &DirSizes) {
+        if let (Some(cache), Some(mtime)) = (&self.cache, modified(metadata)) {
+            cache.insert(path, mtime, sizes);
+        }
+    }
+
+    /// Write the cache back to the disk if anything changed.
+    pub fn save(&self) {
+        if let Some(cache) = &self.cache {
+            cache.save();
+        }
+    }
+}
+
+fn modified(metadata: &Metadata) -> Option<(u64, u32)> {
+    let mtime = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    Some((mtime.as_secs(), mtime.subsec_nanos()))
+}
+
+/// The `(device, inode)` identifying a file that has more than one hard link.
+#[cfg(unix)]
+pub fn hard_link_key(metadata: &Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+
+    if metadata.is_file() && metadata.nlink() > 1 {
+        Some((metadata.dev(), metadata.ino()))
+    } else {
+        None
+    }
+}
+
+#[cfg(not(unix))]
+pub fn hard_link_key(_: &Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+/// The id of the device holding a file.
+#[cfg(unix)]
+pub fn device(metadata: &Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+
+    Some(metadata.dev())
+}
+
+#[cfg(not(unix))]
+pub fn device(_: &Metadata) -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CachedDir, DirSizes, MAX_ENTRIES, Size, SizeAccumulator, SizeCache, TreeSize};
+    use crate::flags::{Flags, OneFileSystem};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_tree_size_counts_hard_links_once() {
+        let mut a = TreeSize::new(Size::new(10));
+        a.add_hard_link((1, 2), Size::new(100));
+        let mut b = TreeSize::new(Size::new(20));
+        b.add_hard_link((1, 2), Size::new(100));
+        b.add_hard_link((1, 3), Size::new(5));
+
+        assert_eq!(110, a.total().get_bytes());
+        assert_eq!(125, b.total().get_bytes());
+        assert_eq!(135, a.clone().merge(b.clone()).total().get_bytes());
+        assert_eq!(a.clone().merge(b.clone()), b.merge(a));
+    }
+
+    #[test]
+    fn test_skips_device() {
+        let accumulator = SizeAccumulator::new(&Flags::default());
+        assert!(!accumulator.skips_device(Some(1), Some(2)));
+
+        let accumulator = SizeAccumulator::new(&Flags {
+            one_file_system: OneFileSystem(true),
+            ..Default::default()
+        });
+        assert!(!accumulator.skips_device(Some(1), Some(1)));
+        assert!(!accumulator.skips_device(Some(1), None));
+        assert!(accumulator.skips_device(Some(1), Some(2)));
+    }
+
+    #[test]
+    fn test_cache_round_trip() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cache_file = tmp.path().join("cache.json");
+        let dir = tmp.path().join("dir");
+        std::fs::create_dir(&dir).unwrap();
+        // make the directory old enough to be trusted
+        let old = std::time::SystemTime::now() - std::time::Duration::from_secs(60);
+        std::fs::File::open(&dir)
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+        let metadata = dir.metadata().unwrap();
+
+        let sizes = DirSizes {
+            size: Size::with_allocated(42, 4096),
+            hard_links: vec![(1, 2, Size::new(3))],
+            subdirs: vec!["sub".to_string()],
+        };
+
+        let accumulator = SizeAccumulator::with_cache_file(cache_file.clone());
+        assert_eq!(None, accumulator.cached(&dir, &metadata));
+        accumulator.store(&dir, &metadata, &sizes);
+        accumulator.save();
+
+        let accumulator = SizeAccumulator::with_cache_file(cache_file);
+        assert_eq!(Some(sizes.clone()), accumulator.cached(&dir, &metadata));
+
+        // the same directory reached through another path
+        std::fs::create_dir(dir.join("sub")).unwrap();
+        std::fs::File::open(&dir)
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+        let metadata = dir.metadata().unwrap();
+        accumulator.store(&dir, &metadata, &sizes);
+        assert_eq!(
+            Some(sizes),
+            accumulator.cached(&dir.join("sub").join(".."), &metadata)
+        );
+
+        // any change to the directory invalidates the entry
+        std::fs::File::create(dir.join("new")).unwrap();
+        assert_eq!(None, accumulator.cached(&dir, &dir.metadata().unwrap()));
+    }
+
+    #[test]
+    fn test_cache_eviction() {
+        let now = 1_000_000_000;
+        let day = 24 * 60 * 60;
+        let cached = |used| CachedDir {
+            mtime: (0, 0),
+            used,
+            sizes: DirSizes::default(),
+        };
+
+        let mut dirs: HashMap<String, CachedDir> = (0..MAX_ENTRIES as u64 + 10)
+            .map(|i| (format!("/dir{i}"), cached(now - i)))
+            .collect();
+        dirs.insert("/old".to_string(), cached(now - 31 * day));
+        SizeCache::evict(&mut dirs, now);
+
+        assert_eq!(MAX_ENTRIES, dirs.len());
+        assert!(!dirs.contains_key("/old"));
+        assert!(dirs.contains_key("/dir0"));
+        assert!(!dirs.contains_key(&format!("/dir{}", MAX_ENTRIES)));
+    }
+}
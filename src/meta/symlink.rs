@@ -43,6 +43,12 @@ impl From<&Path> for SymLink {
 }
 
 impl SymLink {
+    /// A link whose target and validity are known without reading the disk, or no link at all.
+    #[cfg_attr(feature = "no-git", allow(dead_code))]
+    pub fn new(target: Option<String>, valid: bool) -> Self {
+        Self { target, valid }
+    }
+
     pub fn symlink_string(&self) -> Option<String> {
         self.target.as_ref().map(|target| target.to_string())
     }
@@ -1,4 +1,4 @@
-use crate::flags::{DirGrouping, Flags, SortColumn, SortOrder};
+use crate::flags::{DirGrouping, Flags, SizeMode, SortColumn, SortOrder, TimeField};
 use crate::meta::Meta;
 use std::cmp::Ordering;
 use vsort::compare;
@@ -19,11 +19,20 @@ pub fn assemble_sorters(flags: &Flags) -> Vec<(SortOrder, SortFn)> {
 
     match flags.sorting.column {
         SortColumn::Name => sorters.push((flags.sorting.order, by_name)),
-        SortColumn::Size => sorters.push((flags.sorting.order, by_size)),
-        SortColumn::Time => sorters.push((flags.sorting.order, by_date)),
+        SortColumn::Size => match flags.size_mode {
+            SizeMode::Apparent => sorters.push((flags.sorting.order, by_size)),
+            SizeMode::Allocated => sorters.push((flags.sorting.order, by_allocated_size)),
+        },
+        SortColumn::Time => match flags.time {
+            TimeField::Modified => sorters.push((flags.sorting.order, by_date)),
+            TimeField::Accessed => sorters.push((flags.sorting.order, by_access_date)),
+            TimeField::Changed => sorters.push((flags.sorting.order, by_change_date)),
+            TimeField::Birth => sorters.push((flags.sorting.order, by_birth_date)),
+        },
         SortColumn::Version => sorters.push((flags.sorting.order, by_version)),
         SortColumn::Extension => sorters.push((flags.sorting.order, by_extension)),
         SortColumn::GitStatus => sorters.push((flags.sorting.order, by_git_status)),
+        SortColumn::Hash => sorters.push((flags.sorting.order, by_hash)),
         SortColumn::None => {}
     }
     sorters
@@ -57,12 +66,37 @@ fn by_size(a: &Meta, b: &Meta) -> Ordering {
     }
 }
 
+fn by_allocated_size(a: &Meta, b: &Meta) -> Ordering {
+    match (&a.size, &b.size) {
+        (Some(a_size), Some(b_size)) => b_size.get_allocated().cmp(&a_size.get_allocated()),
+        (Some(_), None) => Ordering::Greater,
+        (None, Some(_)) => Ordering::Less,
+        (None, None) => Ordering::Equal,
+    }
+}
+
 fn by_name(a: &Meta, b: &Meta) -> Ordering {
     a.name.cmp(&b.name)
 }
 
+fn by_time(a: &Meta, b: &Meta, time: TimeField) -> Ordering {
+    b.date(time).cmp(&a.date(time)).then(a.name.cmp(&b.name))
+}
+
 fn by_date(a: &Meta, b: &Meta) -> Ordering {
-    b.date.cmp(&a.date).then(a.name.cmp(&b.name))
+    by_time(a, b, TimeField::Modified)
+}
+
+fn by_access_date(a: &Meta, b: &Meta) -> Ordering {
+    by_time(a, b, TimeField::Accessed)
+}
+
+fn by_change_date(a: &Meta, b: &Meta) -> Ordering {
+    by_time(a, b, TimeField::Changed)
+}
+
+fn by_birth_date(a: &Meta, b: &Meta) -> Ordering {
+    by_time(a, b, TimeField::Birth)
 }
 
 fn by_version(a: &Meta, b: &Meta) -> Ordering {
@@ -77,10 +111,22 @@ fn by_git_status(a: &Meta, b: &Meta) -> Ordering {
     a.git_status.cmp(&b.git_status)
 }
 
+/// Cluster the files with the same content together, before the entries that were not hashed.
+fn by_hash(a: &Meta, b: &Meta) -> Ordering {
+    match (&a.content_hash, &b.content_hash) {
+        (Some(a_hash), Some(b_hash)) => a_hash.cmp(b_hash),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+    .then(a.name.cmp(&b.name))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::flags::{Flags, PermissionFlag};
+    use crate::flags::blocks::Block;
+    use crate::flags::{Blocks, Flags, PermissionFlag};
     use std::fs::{File, create_dir};
     use std::io::prelude::*;
     use std::process::Command;
@@ -419,4 +465,60 @@ mod tests {
         assert_eq!(by_size(&meta_c, &meta_b), Ordering::Less);
         assert_eq!(by_size(&meta_c, &meta_c), Ordering::Equal);
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_sort_by_allocated_size() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        // a sparse file is larger than a small file but allocates fewer blocks
+        let path_a = tmp_dir.path().join("aaa.aa");
+        File::create(&path_a)
+            .expect("failed to create file")
+            .set_len(1024 * 1024)
+            .expect("failed to extend file");
+        let meta_a =
+            Meta::from_path(&path_a, false, PermissionFlag::Rwx).expect("failed to get meta");
+
+        let path_b = tmp_dir.path().join("bbb.bb");
+        File::create(&path_b)
+            .expect("failed to create file")
+            .write_all(b"1, 2, 3")
+            .expect("failed to write file");
+        let meta_b =
+            Meta::from_path(&path_b, false, PermissionFlag::Rwx).expect("failed to get meta");
+
+        assert_eq!(by_size(&meta_a, &meta_b), Ordering::Less);
+        assert_eq!(by_allocated_size(&meta_a, &meta_b), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_sort_by_hash() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let flags = Flags {
+            blocks: Blocks(vec![Block::Hash]),
+            ..Flags::default()
+        };
+
+        let mut metas: Vec<Meta> = [("aaa", "one"), ("bbb", "two"), ("ccc", "one")]
+            .iter()
+            .map(|(name, content)| {
+                let path = tmp_dir.path().join(name);
+                std::fs::write(&path, content).expect("failed to write file");
+                let mut meta =
+                    Meta::from_path(&path, false, PermissionFlag::Rwx).expect("failed to get meta");
+                meta.read_block_data(&flags);
+                meta
+            })
+            .collect();
+        let dir = tmp_dir.path().join("ddd");
+        create_dir(&dir).expect("failed to create dir");
+        metas.push(Meta::from_path(&dir, false, PermissionFlag::Rwx).expect("failed to get meta"));
+
+        metas.sort_by(by_hash);
+        let names: Vec<&str> = metas.iter().map(|m| m.name.file_name()).collect();
+        let first = names.iter().position(|&name| name == "aaa").unwrap();
+        assert_eq!("ccc", names[first + 1]);
+        assert_eq!("ddd", names[3]);
+    }
 }
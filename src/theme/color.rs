@@ -2,6 +2,7 @@
 //! this.
 use crossterm::style::Color;
 use serde::{Deserialize, de::IntoDeserializer};
+use std::collections::HashMap;
 use std::fmt;
 
 fn deserialize_option_color<'de, D>(deserializer: D) -> Result<Option<Color>, D::Error>
@@ -14,6 +15,16 @@ where
     Ok(opt.map(|w| w.0))
 }
 
+fn deserialize_color_map<'de, D>(deserializer: D) -> Result<HashMap<String, Color>, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Wrapper(#[serde(deserialize_with = "deserialize_color")] Color);
+    let map = HashMap::<String, Wrapper>::deserialize(deserializer)?;
+    Ok(map.into_iter().map(|(key, w)| (key, w.0)).collect())
+}
+
 // Custom color deserialize
 fn deserialize_color<'de, D>(deserializer: D) -> Result<Color, D::Error>
 where
@@ -105,8 +116,20 @@ pub struct ColorTheme {
     pub inode: INode,
     #[serde(deserialize_with = "deserialize_color")]
     pub tree_edge: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub hash: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub entries: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub git_commit: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub git_author: Color,
     pub links: Links,
     pub git_status: GitStatus,
+    /// The colors of the types guessed from the content of the files, like `image/png` or
+    /// `image`, for the names without an extension.
+    #[serde(deserialize_with = "deserialize_color_map")]
+    pub mime: HashMap<String, Color>,
 
     #[serde(skip)]
     pub file_type: FileType,
@@ -133,6 +156,12 @@ pub struct Permission {
     pub acl: Color,
     #[serde(deserialize_with = "deserialize_color")]
     pub context: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub capabilities: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub immutable: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub inode_flag: Color,
 }
 
 #[derive(Debug, Deserialize, PartialEq, Eq)]
@@ -308,6 +337,27 @@ pub struct GitStatus {
     pub modified: Color,
     #[serde(deserialize_with = "deserialize_color")]
     pub conflicted: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub new_commits: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub uninitialized: Color,
+    /// The dimmed color of the names of the ignored entries
+    #[serde(deserialize_with = "deserialize_color")]
+    pub ignored_name: Color,
+    /// The colors of the `--git-summary` header
+    #[serde(deserialize_with = "deserialize_color")]
+    pub branch: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub ahead: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub behind: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub stash: Color,
+    /// The colors of the line counts of the git-diff block
+    #[serde(deserialize_with = "deserialize_color")]
+    pub added: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub removed: Color,
 }
 
 impl Default for Permission {
@@ -321,6 +371,9 @@ impl Default for Permission {
             octal: Color::AnsiValue(6),
             acl: Color::DarkCyan,
             context: Color::Cyan,
+            capabilities: Color::AnsiValue(202), // OrangeRed1
+            immutable: Color::AnsiValue(197),    // DeepPink2
+            inode_flag: Color::AnsiValue(6),
         }
     }
 }
@@ -435,6 +488,15 @@ impl Default for GitStatus {
             renamed: Color::DarkGreen,
             modified: Color::DarkYellow,
             conflicted: Color::DarkRed,
+            new_commits: Color::DarkMagenta,
+            uninitialized: Color::AnsiValue(245), // Grey
+            ignored_name: Color::AnsiValue(240),  // Grey35
+            branch: Color::DarkCyan,
+            ahead: Color::DarkGreen,
+            behind: Color::DarkRed,
+            stash: Color::DarkYellow,
+            added: Color::DarkGreen,
+            removed: Color::DarkRed,
         }
     }
 }
@@ -458,8 +520,13 @@ impl ColorTheme {
             size: Size::default(),
             inode: INode::default(),
             links: Links::default(),
-            tree_edge: Color::AnsiValue(245), // Grey
+            tree_edge: Color::AnsiValue(245),  // Grey
+            hash: Color::AnsiValue(109),       // LightSkyBlue4
+            entries: Color::AnsiValue(116),    // DarkSlateGray3
+            git_commit: Color::AnsiValue(178), // Gold3
+            git_author: Color::AnsiValue(146), // LightSteelBlue3
             git_status: Default::default(),
+            mime: HashMap::new(),
         }
     }
 }
@@ -15,6 +15,8 @@ pub struct GitThemeSymbols {
     pub ignored: String,
     pub typechange: String,
     pub conflicted: String,
+    pub new_commits: String,
+    pub uninitialized: String,
 }
 
 impl Default for GitThemeSymbols {
@@ -30,6 +32,8 @@ impl Default for GitThemeSymbols {
             ignored: "I".into(),
             typechange: "T".into(),
             conflicted: "C".into(),
+            new_commits: "+".into(),
+            uninitialized: "U".into(),
         }
     }
 }
@@ -44,6 +44,9 @@ pub struct IconTheme {
     pub name: HashMap<String, String>,
     #[serde(deserialize_with = "deserialize_by_extension")]
     pub extension: HashMap<String, String>,
+    /// The icons of the types guessed from the content of the files, like `image/png` or
+    /// `image`, used when neither the name nor the extension has an icon.
+    pub mime: HashMap<String, String>,
     pub filetype: ByType,
 }
 
@@ -62,6 +65,8 @@ pub struct ByType {
     pub special: String,
     pub symlink_dir: String,
     pub symlink_file: String,
+    /// The directories of the Git submodules, when the Git status is read
+    pub submodule: String,
 }
 
 impl Default for IconTheme {
@@ -69,6 +74,7 @@ impl Default for IconTheme {
         IconTheme {
             name: Self::get_default_icons_by_name(),
             extension: Self::get_default_icons_by_extension(),
+            mime: HashMap::new(),
             filetype: ByType::default(),
         }
     }
@@ -87,6 +93,7 @@ impl Default for ByType {
             device_char: "\u{e601}".into(),   // 
             device_block: "\u{f072b}".into(), // 󰜫
             special: "\u{f2dc}".into(),       // 
+            submodule: "\u{f414}".into(),     // 
         }
     }
 }
@@ -104,6 +111,7 @@ impl ByType {
             device_char: "\u{1f5a8}".into(),
             device_block: "\u{1f4bd}".into(),
             special: "\u{1f4df}".into(),
+            submodule: "\u{1f4e6}".into(),
         }
     }
 }
@@ -113,6 +121,7 @@ impl IconTheme {
         IconTheme {
             name: HashMap::new(),
             extension: HashMap::new(),
+            mime: HashMap::new(),
             filetype: ByType::unicode(),
         }
     }
@@ -616,6 +616,597 @@ fn test_tree_dereference() {
         );
 }
 
+#[test]
+fn test_format_json() {
+    let dir = tempdir();
+    dir.child("one").write_str("abc").unwrap();
+    dir.child("two").create_dir_all().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--format")
+        .arg("json")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::contains(r#""name": "one""#))
+        .stdout(predicate::str::contains(r#""size": 3"#))
+        .stdout(predicate::str::contains(r#""file_type": "directory""#));
+}
+
+#[test]
+fn test_format_ndjson() {
+    let dir = tempdir();
+    dir.child("one").touch().unwrap();
+    dir.child("two").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--format")
+        .arg("ndjson")
+        .arg(dir.path())
+        .assert()
+        .stdout(
+            predicate::str::is_match(r#"(?m)^\{"name":"one",.*\}\n\{"name":"two",.*\}\n$"#)
+                .unwrap(),
+        );
+}
+
+#[test]
+fn test_format_csv() {
+    let dir = tempdir();
+    dir.child("a, b").write_binary(&[0u8; 12]).unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--color")
+        .arg("always")
+        .arg("--format")
+        .arg("csv")
+        .arg("--blocks")
+        .arg("size,name")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("Size,Name\n12,\"a, b\"\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--format")
+        .arg("tsv")
+        .arg("--blocks")
+        .arg("size,name")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("Size\tName\n12\ta, b\n"));
+}
+
+#[test]
+fn test_tree_threads_same_output() {
+    let tmp = tempdir();
+    for d in ["one", "two", "three"] {
+        tmp.child(d).create_dir_all().unwrap();
+        tmp.child(format!("{d}/a")).touch().unwrap();
+        tmp.child(format!("{d}/b")).touch().unwrap();
+    }
+
+    let sequential = cmd()
+        .arg("--tree")
+        .arg("--ignore-config")
+        .arg(tmp.path())
+        .output()
+        .unwrap();
+
+    cmd()
+        .arg("--tree")
+        .arg("--threads")
+        .arg("4")
+        .arg("--ignore-config")
+        .arg(tmp.path())
+        .assert()
+        .stdout(predicate::eq(sequential.stdout));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_total_size_hard_links_threads() {
+    let tmp = tempdir();
+    tmp.child("a/file").write_binary(&[0u8; 100]).unwrap();
+    tmp.child("b").create_dir_all().unwrap();
+    std::fs::hard_link(tmp.child("a/file").path(), tmp.child("b/link").path()).unwrap();
+    let dir_size = |name: &str| tmp.child(name).path().metadata().unwrap().len();
+
+    // each directory counts the file once, whichever thread reaches it first
+    let expected = format!(
+        "Size\tName\n{}\ta\n{}\tb\n",
+        dir_size("a") + 100,
+        dir_size("b") + 100
+    );
+    for _ in 0..10 {
+        cmd()
+            .arg("--ignore-config")
+            .arg("--total-size")
+            .arg("--threads")
+            .arg("4")
+            .arg("--format")
+            .arg("tsv")
+            .arg("--blocks")
+            .arg("size,name")
+            .arg(tmp.path())
+            .assert()
+            .stdout(predicate::eq(expected.as_str()));
+    }
+}
+
+#[test]
+fn test_tree_gitignore() {
+    let tmp = tempdir();
+    tmp.child(".ignore").write_str("target/\n*.log\n").unwrap();
+    tmp.child("one.log").touch().unwrap();
+    tmp.child("one.d").create_dir_all().unwrap();
+    tmp.child("one.d/two").touch().unwrap();
+    tmp.child("target/debug").touch().unwrap();
+
+    cmd()
+        .arg(tmp.path())
+        .arg("--tree")
+        .arg("--gitignore")
+        .arg("--ignore-config")
+        .assert()
+        .stdout(predicate::str::is_match("└── one.d\n    └── two\n$").unwrap());
+}
+
+#[test]
+fn test_tree_ext_prunes_empty_dirs() {
+    let tmp = tempdir();
+    tmp.child("one.rs").touch().unwrap();
+    tmp.child("one.d/two.rs").touch().unwrap();
+    tmp.child("one.d/two.toml").touch().unwrap();
+    tmp.child("docs/three.md").touch().unwrap();
+
+    cmd()
+        .arg(tmp.path())
+        .arg("--tree")
+        .arg("--ext")
+        .arg("rs")
+        .arg("--ignore-config")
+        .assert()
+        .stdout(predicate::str::is_match("├── one.d\n│   └── two.rs\n└── one.rs\n$").unwrap());
+}
+
+#[test]
+fn test_type_directories_only() {
+    let tmp = tempdir();
+    tmp.child("one").touch().unwrap();
+    tmp.child("two.d").create_dir_all().unwrap();
+
+    cmd()
+        .arg(tmp.path())
+        .arg("--type")
+        .arg("d")
+        .arg("--ignore-config")
+        .assert()
+        .stdout(predicate::eq("two.d\n"));
+}
+
+#[test]
+fn test_filters_file_arguments() {
+    let tmp = tempdir();
+    tmp.child("one.rs").touch().unwrap();
+    tmp.child("two.md").touch().unwrap();
+    tmp.child("src/three.rs").touch().unwrap();
+    tmp.child("src/four.md").touch().unwrap();
+
+    cmd()
+        .current_dir(tmp.path())
+        .arg("one.rs")
+        .arg("two.md")
+        .arg("src")
+        .arg("--ext")
+        .arg("rs")
+        .arg("--ignore-config")
+        .assert()
+        .stdout(predicate::eq(
+            "one.rs
+
+src:
+three.rs
+",
+        ));
+
+    cmd()
+        .current_dir(tmp.path())
+        .arg("one.rs")
+        .arg("two.md")
+        .arg("--include-glob")
+        .arg("*.md")
+        .arg("--ignore-config")
+        .assert()
+        .stdout(predicate::eq(
+            "two.md
+",
+        ));
+}
+
+#[test]
+fn test_size_and_time_predicates() {
+    let tmp = tempdir();
+    tmp.child("small").write_str("a").unwrap();
+    tmp.child("big").write_binary(&[0; 4096]).unwrap();
+    tmp.child("old").write_binary(&[0; 4096]).unwrap();
+    let old = std::time::SystemTime::now() - std::time::Duration::from_secs(3 * 24 * 60 * 60);
+    std::fs::File::options()
+        .write(true)
+        .open(tmp.child("old").path())
+        .unwrap()
+        .set_modified(old)
+        .unwrap();
+
+    cmd()
+        .arg(tmp.path())
+        .arg("--min-size")
+        .arg("2K")
+        .arg("--newer")
+        .arg("2d")
+        .arg("--ignore-config")
+        .assert()
+        .stdout(predicate::eq("big\n"));
+
+    cmd()
+        .arg(tmp.path())
+        .arg("--max-size")
+        .arg("1K")
+        .arg("--ignore-config")
+        .assert()
+        .stdout(predicate::eq("small\n"));
+    cmd()
+        .current_dir(tmp.path())
+        .arg("small")
+        .arg("big")
+        .arg("--min-size")
+        .arg("2K")
+        .arg("--ignore-config")
+        .assert()
+        .stdout(predicate::eq("big\n"));
+}
+
+#[test]
+fn test_sort_by_access_time() {
+    let tmp = tempdir();
+    tmp.child("accessed").touch().unwrap();
+    tmp.child("modified").touch().unwrap();
+    let old = std::time::SystemTime::now() - std::time::Duration::from_secs(24 * 60 * 60);
+    let set_times = |name: &str, times: std::fs::FileTimes| {
+        std::fs::File::options()
+            .write(true)
+            .open(tmp.child(name).path())
+            .unwrap()
+            .set_times(times)
+            .unwrap();
+    };
+    set_times("accessed", std::fs::FileTimes::new().set_modified(old));
+    set_times("modified", std::fs::FileTimes::new().set_accessed(old));
+
+    cmd()
+        .arg(tmp.path())
+        .arg("-t")
+        .arg("--ignore-config")
+        .assert()
+        .stdout(predicate::eq("modified\naccessed\n"));
+
+    cmd()
+        .arg(tmp.path())
+        .arg("-tu")
+        .arg("--ignore-config")
+        .assert()
+        .stdout(predicate::eq("accessed\nmodified\n"));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_list_extended_attributes() {
+    let tmp = tempdir();
+    tmp.child("tagged").touch().unwrap();
+    tmp.child("plain").touch().unwrap();
+    // not every filesystem backing the temporary directory supports user attributes
+    if xattr::set(tmp.child("tagged").path(), "user.origin", b"test").is_err() {
+        return;
+    }
+
+    cmd()
+        .arg(tmp.path())
+        .arg("-l@")
+        .arg("--blocks")
+        .arg("xattrs,name")
+        .arg("--ignore-config")
+        .assert()
+        .stdout(predicate::eq("0 plain\n1 tagged\n\tuser.origin\t     4\n"));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_list_acl_entries() {
+    let tmp = tempdir();
+    tmp.child("shared").touch().unwrap();
+    // user::rw- user:0:rwx group::r-- mask::r-- other::---
+    let mut acl = 2u32.to_le_bytes().to_vec();
+    for (tag, perm, id) in [
+        (1u16, 6u16, u32::MAX),
+        (2, 7, 0),
+        (4, 4, u32::MAX),
+        (0x10, 4, u32::MAX),
+        (0x20, 0, u32::MAX),
+    ] {
+        acl.extend(tag.to_le_bytes());
+        acl.extend(perm.to_le_bytes());
+        acl.extend(id.to_le_bytes());
+    }
+    // ACLs need a filesystem supporting them
+    if xattr::set(tmp.child("shared").path(), "system.posix_acl_access", &acl).is_err() {
+        return;
+    }
+
+    cmd()
+        .arg(tmp.path())
+        .arg("-l")
+        .arg("--acl")
+        .arg("--blocks")
+        .arg("name")
+        .arg("--ignore-config")
+        .assert()
+        .stdout(predicate::eq(
+            "shared\n\tuser::rw-\n\tuser:root:rwx\t#effective:r--\n\tgroup::r--\n\tmask::r--\n\tother::---\n",
+        ));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_list_capabilities() {
+    let tmp = tempdir();
+    tmp.child("ping").touch().unwrap();
+    tmp.child("plain").touch().unwrap();
+    // cap_net_raw=ep
+    let caps: Vec<u8> = [0x0200_0001u32, 1 << 13, 0, 0, 0]
+        .iter()
+        .flat_map(|word| word.to_le_bytes())
+        .collect();
+    // setting capabilities needs CAP_SETFCAP
+    if xattr::set(tmp.child("ping").path(), "security.capability", &caps).is_err() {
+        return;
+    }
+
+    cmd()
+        .arg(tmp.path())
+        .arg("-l")
+        .arg("--blocks")
+        .arg("caps,name")
+        .arg("--ignore-config")
+        .assert()
+        .stdout(predicate::eq("cap_net_raw=ep ping\n-              plain\n"));
+}
+
+#[test]
+fn test_hash_sort_clusters_identical_files() {
+    let tmp = tempdir();
+    tmp.child("a").write_str("abc").unwrap();
+    tmp.child("b").write_str("other").unwrap();
+    tmp.child("c").write_str("abc").unwrap();
+    tmp.child("d").create_dir_all().unwrap();
+
+    cmd()
+        .arg(tmp.path())
+        .arg("-l")
+        .arg("--blocks")
+        .arg("name")
+        .arg("--hash")
+        .arg("crc32")
+        .arg("--sort")
+        .arg("hash")
+        .arg("--ignore-config")
+        .assert()
+        .stdout(predicate::eq(
+            "352441c2 a\n352441c2 c\nd9583520 b\n-        d\n",
+        ));
+}
+
+#[test]
+fn test_duplicates() {
+    let tmp = tempdir();
+    tmp.child("a").write_str("same").unwrap();
+    tmp.child("sub/b").write_str("same").unwrap();
+    tmp.child("c").write_str("diff").unwrap();
+    tmp.child("empty").touch().unwrap();
+    tmp.child("sub/empty").touch().unwrap();
+
+    for threads in ["1", "4"] {
+        cmd()
+            .current_dir(tmp.path())
+            .arg(".")
+            .arg("--duplicates")
+            .arg("--threads")
+            .arg(threads)
+            .arg("--ignore-config")
+            .assert()
+            .stdout(predicate::eq(
+                "2 files of 4 B, 4 B wasted:\n./a\n./sub/b\n\n1 group of duplicates, 4 B wasted\n",
+            ));
+    }
+}
+
+#[test]
+fn test_duplicates_csv_group() {
+    let tmp = tempdir();
+    tmp.child("a").write_str("same").unwrap();
+    tmp.child("b").write_str("same").unwrap();
+    tmp.child("c").write_str("other").unwrap();
+    tmp.child("d").write_str("other").unwrap();
+
+    cmd()
+        .current_dir(tmp.path())
+        .arg(".")
+        .arg("--duplicates")
+        .arg("--format")
+        .arg("csv")
+        .arg("--blocks")
+        .arg("name")
+        .arg("--ignore-config")
+        .assert()
+        .stdout(predicate::eq("Group,Name\n1,./c\n1,./d\n2,./a\n2,./b\n"));
+}
+
+#[test]
+fn test_mime_block() {
+    let tmp = tempdir();
+    tmp.child("archive")
+        .write_binary(b"\x1f\x8b\x08\0")
+        .unwrap();
+    tmp.child("notes").write_str("hello\n").unwrap();
+    tmp.child("run")
+        .write_str("#!/bin/sh\necho hello\n")
+        .unwrap();
+    tmp.child("dir").create_dir_all().unwrap();
+
+    cmd()
+        .arg(tmp.path())
+        .arg("-l")
+        .arg("--blocks")
+        .arg("mime,name")
+        .arg("--ignore-config")
+        .assert()
+        .stdout(predicate::eq(
+            "application/gzip   archive\n-                  dir\ntext/plain         notes\ntext/x-shellscript run\n",
+        ));
+}
+
+#[test]
+fn test_dir_count() {
+    let tmp = tempdir();
+    tmp.child("many/a").touch().unwrap();
+    tmp.child("many/b").touch().unwrap();
+    tmp.child("many/.hidden").touch().unwrap();
+    tmp.child("one/a").touch().unwrap();
+    tmp.child("file").write_str("abc").unwrap();
+
+    cmd()
+        .arg(tmp.path())
+        .arg("-l")
+        .arg("--blocks")
+        .arg("size,name")
+        .arg("--dir-count")
+        .arg("--ignore-config")
+        .assert()
+        .stdout(predicate::eq(
+            "3 B       file\n2 entries many\n1 entry   one\n",
+        ));
+
+    cmd()
+        .arg(tmp.path())
+        .arg("-lA")
+        .arg("--blocks")
+        .arg("entries,name")
+        .arg("--ignore-config")
+        .assert()
+        .stdout(predicate::eq("- file\n3 many\n1 one\n"));
+}
+
+#[test]
+fn test_dir_count_same_in_every_layout() {
+    let tmp = tempdir();
+    tmp.child("ec/a.txt").touch().unwrap();
+    tmp.child("ec/b.txt").touch().unwrap();
+    tmp.child("ec/c.rs").touch().unwrap();
+
+    cmd()
+        .current_dir(tmp.path())
+        .arg("-l")
+        .arg("--blocks")
+        .arg("entries,name")
+        .arg("-I")
+        .arg("*.txt")
+        .arg("--ignore-config")
+        .assert()
+        .stdout(predicate::eq("3 ec\n"));
+
+    cmd()
+        .current_dir(tmp.path())
+        .arg("--tree")
+        .arg("--blocks")
+        .arg("entries,name")
+        .arg("-I")
+        .arg("*.txt")
+        .arg("--ignore-config")
+        .assert()
+        .stdout(predicate::eq("1 .\n3 └── ec\n-     └── c.rs\n"));
+}
+
+#[test]
+fn test_invalid_size_predicate() {
+    cmd()
+        .arg("--min-size")
+        .arg("huge")
+        .arg("--ignore-config")
+        .assert()
+        .failure();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_unknown_user_predicate() {
+    cmd()
+        .arg("--user")
+        .arg("no-such-user")
+        .arg("--ignore-config")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown user 'no-such-user'"));
+}
+
+#[test]
+fn test_format_template() {
+    let tmp = tempdir();
+    tmp.child("a").write_binary(&[0u8; 1234]).unwrap();
+    tmp.child("bb").write_binary(&[0u8; 5]).unwrap();
+
+    cmd()
+        .arg(tmp.path())
+        .arg("--format-template")
+        .arg("{size:>6b} | {name}")
+        .arg("--ignore-config")
+        .assert()
+        .stdout(predicate::eq("  1234 | a\n     5 | bb\n"));
+
+    cmd()
+        .arg(tmp.path())
+        .arg("--format-template")
+        .arg("{size:b}|{name}")
+        .arg("--ignore-config")
+        .assert()
+        .stdout(predicate::eq("1234|a\n5   |bb\n"));
+}
+
+#[test]
+fn test_invalid_format_template() {
+    cmd()
+        .arg("--format-template")
+        .arg("{name} {colour}")
+        .arg("--ignore-config")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown field 'colour'"));
+}
+
+#[test]
+fn test_format_template_with_tree() {
+    cmd()
+        .arg("--format-template")
+        .arg("{size} | {name}")
+        .arg("--tree")
+        .arg("--ignore-config")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--format-template cannot be used with --tree",
+        ));
+}
+
 fn cmd() -> Command {
     Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap()
 }